@@ -0,0 +1,105 @@
+use eframe::egui::{ScrollArea, Ui, Window};
+use stickere::backup::Backup;
+use stickere::storage::Storage;
+use stickere::error::StickereError;
+use crate::ui_utils::UiUtils;
+
+/// A struct that represents the restore from backup UI component.
+/// It lists the backups of the database, taken when it is opened and before collections lose data,
+/// and replaces the database with the backup chosen. The restore is carried out by the storage,
+/// which backs up the current data first, while the application starts over with the restored data.
+pub struct BackupDialog {
+    open: bool,
+    backups: Option<Vec<Backup>>,
+    restore_candidate: Option<Backup>,
+    confirm_restore: bool,
+}
+
+impl Default for BackupDialog {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BackupDialog {
+    /// Creates a new `BackupDialog` instance.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new instance of `BackupDialog`.
+    pub fn new() -> Self {
+        Self {
+            open: false,
+            backups: None,
+            restore_candidate: None,
+            confirm_restore: false,
+        }
+    }
+
+    /// Renders the button opening the dialog and, while it is open, the dialog listing the backups,
+    /// restoring the backup chosen once confirmed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, StickereError>` - Whether a backup was restored, or the error of listing or restoring
+    ///   the backups.
+    pub fn ui(&mut self, ui: &mut Ui, db_handler: &mut dyn Storage) -> Result<bool, StickereError> {
+        if ui.button("Restaureaza din backup").clicked() {
+            // The backups are listed again whenever the dialog is opened, to show the backups taken meanwhile.
+            self.backups = None;
+            self.open = true;
+        }
+
+        if self.confirm_restore {
+            let prompt = format!("Inlocuiesti toate datele cu backup-ul din {}? Datele curente sunt salvate intr-un \
+                                  backup nou.", self.restore_candidate.as_ref().map_or("", |backup| &backup.taken_at));
+            if UiUtils::confirm(ui.ctx(), &mut self.confirm_restore, "Restaureaza din backup", &prompt) {
+                if let Some(backup) = self.restore_candidate.take() {
+                    db_handler.restore_backup(&backup)?;
+                    self.backups = None;
+                    self.open = false;
+                    return Ok(true);
+                }
+            }
+        }
+
+        if !self.open {
+            return Ok(false);
+        }
+
+        let backups = match self.backups.take() {
+            Some(backups) => backups,
+            None => db_handler.get_backups()?,
+        };
+
+        Window::new("Restaureaza din backup")
+            .open(&mut self.open)
+            .collapsible(false)
+            .show(ui.ctx(), |ui| {
+                if backups.is_empty() {
+                    ui.label("Nu exista niciun backup.");
+                }
+
+                ScrollArea::vertical().max_height(300.0).show(ui, |ui| {
+                    for backup in &backups {
+                        ui.horizontal(|ui| {
+                            ui.label(&backup.taken_at)
+                                .on_hover_text(backup.path.display().to_string());
+                            if ui.button("Restaureaza").clicked() {
+                                self.restore_candidate = Some(backup.clone());
+                                self.confirm_restore = true;
+                            }
+                        });
+                    }
+                });
+            });
+        self.backups = Some(backups);
+
+        Ok(false)
+    }
+}
@@ -0,0 +1,290 @@
+//! A command-line interface to the sticker database, for scripting updates or working on a machine without a display.
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+use clap::{Parser, Subcommand, ValueEnum};
+use stickere::checklist::ExportFormat;
+use stickere::collection::Collection;
+use stickere::db::DbHandler;
+use stickere::error::StickereError;
+use stickere::profile::{self, DbLocation, Profiles};
+use stickere::storage::Storage;
+use stickere::trade_list::{self, TradeList};
+
+/// Manage sticker collections from the command line.
+#[derive(Parser)]
+#[command(name = "stickere-cli", version)]
+struct Cli {
+    /// The sticker database file, instead of the database of a profile.
+    #[arg(long, conflicts_with = "profile")]
+    db: Option<PathBuf>,
+
+    /// The profile whose database is used, `default` if neither this nor the `STICKERE_DB` environment variable is set.
+    #[arg(long)]
+    profile: Option<String>,
+
+    #[command(subcommand)]
+    command: Command,
+}
+
+/// The subcommands of the command-line interface.
+/// Collections are given by their name or identifier, as shown by `list`.
+#[derive(Subcommand)]
+enum Command {
+    /// List the collections with their progress.
+    List,
+    /// Show every card of a collection.
+    Show {
+        collection: String,
+    },
+    /// Mark cards as collected, e.g. `mark "Euro 2024" 3 7 12-19`.
+    Mark {
+        collection: String,
+        #[arg(required = true)]
+        labels: Vec<String>,
+    },
+    /// Mark cards as not collected.
+    Unmark {
+        collection: String,
+        #[arg(required = true)]
+        labels: Vec<String>,
+    },
+    /// Add duplicates with `+label` or remove them with `-label`, e.g. `dup "Euro 2024" +5 +12-14 -7`.
+    Dup {
+        collection: String,
+        #[arg(required = true, allow_hyphen_values = true)]
+        changes: Vec<String>,
+    },
+    /// Print the missing cards and duplicates of a collection as a trade list.
+    Needs {
+        collection: String,
+    },
+    /// Export a collection to CSV or JSON.
+    Export {
+        collection: String,
+        #[arg(long, value_enum, default_value_t = Format::Json)]
+        format: Format,
+        /// The file to write, instead of printing the export.
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+    },
+}
+
+/// The export formats selectable from the command line.
+#[derive(Clone, Copy, ValueEnum)]
+enum Format {
+    Csv,
+    Json,
+}
+
+impl From<Format> for ExportFormat {
+    fn from(format: Format) -> Self {
+        match format {
+            Format::Csv => ExportFormat::Csv,
+            Format::Json => ExportFormat::Json,
+        }
+    }
+}
+
+/// Parses the command line, runs the subcommand and exits with an error code if it failed.
+fn main() {
+    let cli = Cli::parse();
+
+    if let Err(err) = run(cli) {
+        eprintln!("error: {err}");
+        process::exit(1);
+    }
+}
+
+/// Runs a subcommand against the database.
+///
+/// # Arguments
+///
+/// * `cli` - The parsed command line.
+///
+/// # Returns
+///
+/// * `Result<(), StickereError>` - The error of the subcommand, if any.
+fn run(cli: Cli) -> Result<(), StickereError> {
+    let location = DbLocation::resolve(cli.db, cli.profile, env::var_os(profile::DB_PATH_VAR));
+    let profiles = Profiles::in_data_dir();
+    let mut db_handler = location.open(&profiles)?;
+    if let Some(path) = profiles.take_skipped_legacy_db()? {
+        eprintln!("note: the old database {} was not copied to the {} profile, which already had a database; \
+                   open it with --db", path.display(), profile::DEFAULT_PROFILE);
+    }
+
+    match cli.command {
+        Command::List => {
+            for collection in db_handler.get_collections()? {
+                let (collected, total) = collection.progress();
+                println!("{}\t{}\t{collected}/{total}", collection.id, collection.name());
+            }
+        }
+        Command::Show { collection } => {
+            let collection = find_collection(&db_handler, &collection)?;
+            let (collected, total) = collection.progress();
+            println!("{} ({collected}/{total})", collection.name());
+
+            for card in db_handler.get_cards_from_collection(collection.id)? {
+                let status = if card.checked { "x" } else { " " };
+                let duplicates = if card.duplicates > 0 { format!(" +{}", card.duplicates) } else { String::new() };
+                let name = if card.details.name.is_empty() { String::new() } else { format!("  {}", card.details.name) };
+                println!("[{status}] {}{duplicates}{name}", card.label);
+            }
+        }
+        Command::Mark { collection, labels } => mark(&mut db_handler, &collection, &labels, true)?,
+        Command::Unmark { collection, labels } => mark(&mut db_handler, &collection, &labels, false)?,
+        Command::Dup { collection, changes } => {
+            let collection = find_collection(&db_handler, &collection)?;
+            let changes = parse_duplicate_changes(&changes)?;
+            db_handler.change_duplicates(collection.id, &changes)?;
+            println!("{} cards updated", changes.len());
+        }
+        Command::Needs { collection } => {
+            let collection = find_collection(&db_handler, &collection)?;
+            let cards = db_handler.get_cards_from_collection(collection.id)?;
+            let trade_list = TradeList::from_cards(&cards);
+            if trade_list.needs.is_empty() {
+                println!("Nothing missing");
+                if !trade_list.doubles.is_empty() {
+                    println!("Got: {}", trade_list.doubles_text());
+                }
+            } else {
+                println!("{trade_list}");
+            }
+        }
+        Command::Export { collection, format, output } => {
+            let collection = find_collection(&db_handler, &collection)?;
+            let contents = db_handler.export_collection(collection.id, format.into())?;
+            match output {
+                Some(path) => fs::write(path, contents)?,
+                None => print!("{contents}"),
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Marks cards of a collection as collected or not collected.
+///
+/// # Arguments
+///
+/// * `db_handler` - A mutable reference to the `DbHandler` for database operations.
+/// * `collection` - A string slice holding the identifier or the name of the collection.
+/// * `labels` - The label arguments, e.g. `["3", "7", "12-19"]`.
+/// * `collected` - Whether the cards are marked as collected.
+///
+/// # Returns
+///
+/// * `Result<(), StickereError>` - The error of finding the collection or the cards, if any.
+fn mark(db_handler: &mut DbHandler, collection: &str, labels: &[String], collected: bool) -> Result<(), StickereError> {
+    let collection = find_collection(db_handler, collection)?;
+    let labels = parse_labels(labels)?;
+    db_handler.set_collected(collection.id, &labels, collected)?;
+    println!("{} cards updated", labels.len());
+    Ok(())
+}
+
+/// Finds a collection by its identifier or, failing that, by its name, ignoring case.
+///
+/// # Arguments
+///
+/// * `db_handler` - A reference to the `DbHandler` for database operations.
+/// * `name` - A string slice holding the identifier or the name of the collection.
+///
+/// # Returns
+///
+/// * `Result<Collection, StickereError>` - The collection, or `CollectionNotFound` if there is none.
+fn find_collection(db_handler: &DbHandler, name: &str) -> Result<Collection, StickereError> {
+    let mut collections = db_handler.get_collections()?;
+    let id = name.parse::<u32>().ok();
+
+    // The collection with the identifier comes first, ahead of a collection named like an identifier.
+    collections.sort_by_key(|collection| Some(collection.id) != id);
+    collections.into_iter()
+        .find(|collection| Some(collection.id) == id || collection.name().eq_ignore_ascii_case(name))
+        .ok_or_else(|| StickereError::CollectionNotFound(name.to_string()))
+}
+
+/// Expands the labels given as arguments, where each argument may hold several labels and ranges.
+///
+/// # Arguments
+///
+/// * `arguments` - The label arguments, e.g. `["3", "7", "12-19"]`.
+///
+/// # Returns
+///
+/// * `Result<Vec<String>, StickereError>` - The labels, or `InvalidArgument` for an argument that is not a valid label list.
+fn parse_labels(arguments: &[String]) -> Result<Vec<String>, StickereError> {
+    trade_list::parse_labels(&arguments.join(", ")).map_err(|err| StickereError::InvalidArgument(err.to_string()))
+}
+
+/// Reads the duplicate changes given as arguments, `+label` adding and `-label` removing a duplicate.
+///
+/// # Arguments
+///
+/// * `arguments` - The change arguments, e.g. `["+5", "+12-14", "-7"]`.
+///
+/// # Returns
+///
+/// * `Result<Vec<(String, i64)>, StickereError>` - Every label with the number of duplicates to add or remove,
+///   or `InvalidArgument` for an argument that is not a valid change.
+fn parse_duplicate_changes(arguments: &[String]) -> Result<Vec<(String, i64)>, StickereError> {
+    let mut changes = vec![];
+
+    for argument in arguments {
+        let (change, labels) = match argument.split_at_checked(1) {
+            Some(("+", labels)) => (1, labels),
+            Some(("-", labels)) => (-1, labels),
+            _ => return Err(StickereError::InvalidArgument(format!("\"{argument}\", expected +label or -label"))),
+        };
+
+        for label in trade_list::parse_labels(labels).map_err(|err| StickereError::InvalidArgument(err.to_string()))? {
+            changes.push((label, change));
+        }
+    }
+
+    Ok(changes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn changes(arguments: &[&str]) -> Result<Vec<(String, i64)>, StickereError> {
+        parse_duplicate_changes(&arguments.iter().map(|argument| argument.to_string()).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn duplicate_changes_are_expanded() {
+        assert_eq!(changes(&["+5", "+12-14", "-7"]).unwrap(), [
+            ("5".to_string(), 1),
+            ("12".to_string(), 1),
+            ("13".to_string(), 1),
+            ("14".to_string(), 1),
+            ("7".to_string(), -1),
+        ]);
+    }
+
+    #[test]
+    fn invalid_duplicate_changes_are_rejected() {
+        assert!(matches!(changes(&["5"]), Err(StickereError::InvalidArgument(_))));
+        assert!(matches!(changes(&[""]), Err(StickereError::InvalidArgument(_))));
+        assert!(matches!(changes(&["+19-12"]), Err(StickereError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn collection_is_found_by_identifier_before_name() {
+        let mut db_handler = DbHandler::new(":memory:").unwrap();
+        let euro_id = db_handler.add_collection("Euro 2024", 2).unwrap();
+        let numbered_id = db_handler.add_collection(&euro_id.to_string(), 2).unwrap();
+
+        assert_eq!(find_collection(&db_handler, &euro_id.to_string()).unwrap().id, euro_id);
+        assert_eq!(find_collection(&db_handler, &numbered_id.to_string()).unwrap().id, numbered_id);
+        assert_eq!(find_collection(&db_handler, "euro 2024").unwrap().id, euro_id);
+        assert!(matches!(find_collection(&db_handler, "Euro 2020"), Err(StickereError::CollectionNotFound(_))));
+    }
+}
@@ -1,14 +1,22 @@
-use eframe::egui::{TextEdit, Ui};
-use crate::card::Card;
-use crate::db::DbHandler;
-
-use crate::ui_utils::UiUtils;
+use eframe::egui::{Color32, TextEdit, Ui};
+use stickere::card::Card;
+use stickere::card_details::CardDetails;
+use stickere::storage::Storage;
+use stickere::error::StickereError;
+use stickere::history::{Command, History};
+use stickere::label;
 
 /// A struct that represents the card adder UI component.
 /// It is used for adding new cards to a certain collection.
 pub struct CardAdder {
     card_label: String,
-    show_popup: bool,
+    error: Option<String>,
+}
+
+impl Default for CardAdder {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl CardAdder {
@@ -20,7 +28,7 @@ impl CardAdder {
     pub fn new() -> CardAdder {
         CardAdder {
             card_label: String::new(),
-            show_popup: false,
+            error: None,
         }
     }
 
@@ -29,52 +37,108 @@ impl CardAdder {
     /// # Arguments
     ///
     /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
-    /// * `db_handler` - A mutable reference to the `DbHandler` for database operations.
-    /// * `cards` - A mutable reference to a vector of `Card` objects, the collections of 
-    ///             the currently selected cards.
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    /// * `cards` - A mutable reference to a vector of `Card` objects, the collections of
+    ///   the currently selected cards.
     /// * `collection_id` - A `u32` representing the identifier of the current selected collection.
-    pub fn ui(&mut self, ui: &mut Ui, db_handler: &mut DbHandler, cards: &mut Vec<Card>, collection_id: u32) {
-        if self.show_popup {
-            UiUtils::popup(ui.ctx(),
-                           &mut self.show_popup,
-                           "Eroare",
-                           "Numele unui card trebuie sa fie un numar!");
-        }
+    /// * `history` - A mutable reference to the `History` recording the added card.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of adding the new card, if any.
+    ///   A label that already exists in the collection is reported next to the field instead.
+    pub fn ui(&mut self, ui: &mut Ui, db_handler: &mut dyn Storage, cards: &mut Vec<Card>,
+              collection_id: u32, history: &mut History) -> Result<(), StickereError> {
+        let mut result = Ok(());
 
         ui.horizontal_centered(|ui| {
             ui.add(TextEdit::singleline(&mut self.card_label)
-                .hint_text("Numarul unui sticker sau lasa gol")
+                .hint_text("Eticheta unui sticker sau lasa gol")
                 .desired_width(200.0));
 
             let trimmed_text = self.card_label.trim();
             if ui.button("Adauga Sticker").clicked() {
-                match trimmed_text.parse::<u32>() {
-                    Ok(label) => self.add_new_card(label, collection_id, db_handler, cards),
-                    Err(_) => {
-                        if trimmed_text.is_empty() {
-                            self.add_new_card(db_handler.get_max_label_for_collection(collection_id) + 1,
-                                              collection_id,
-                                              db_handler,
-                                              cards)
-                        } else {
-                            self.show_popup = true;
-                        }
-                    }
+                result = if trimmed_text.is_empty() {
+                    db_handler.get_next_label_for_collection(collection_id)
+                        .and_then(|label| self.add_new_card(label, collection_id, db_handler, cards, history))
+                } else {
+                    self.add_new_card(trimmed_text.to_string(), collection_id, db_handler, cards, history)
+                };
+
+                self.error = None;
+                if let Err(StickereError::DuplicateLabel(label)) = &result {
+                    self.error = Some(format!("Stickerul {label} exista deja in catalog"));
+                    result = Ok(());
                 }
             }
+
+            if let Some(message) = &self.error {
+                ui.colored_label(Color32::from_rgb(255, 0, 0), message);
+            }
         });
+
+        result
     }
 
-    /// Adds a new card to the active selected collection.
+    /// Adds a new card to the active selected collection, keeping the cards sorted by label.
     ///
     /// # Arguments
     ///
-    /// * `card_number` - A `u32` representing the card number.
+    /// * `card_label` - A `String` representing the label of the card.
     /// * `collection_id` - A `u32` representing the identifier of the selected collection.
-    /// * `db_handler` - A mutable reference to the `DbHandler` for database operations.
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
     /// * `cards` - A mutable reference to a vector of `Card` objects.
-    fn add_new_card(&self, card_number: u32, collection_id: u32, db_handler: &mut DbHandler, cards: &mut Vec<Card>) {
-        let card_id = db_handler.add_card(card_number, collection_id);
-        cards.push(Card::new(card_number, card_id, false, 0));
+    /// * `history` - A mutable reference to the `History` recording the added card.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn add_new_card(&self, card_label: String, collection_id: u32, db_handler: &mut dyn Storage,
+                    cards: &mut Vec<Card>, history: &mut History) -> Result<(), StickereError> {
+        let card_id = db_handler.add_card(&card_label, collection_id)?;
+        history.push(Command::AddCard { card_id, snapshot: None });
+        cards.push(Card::new(card_label, card_id, false, 0, None, CardDetails::default()));
+        cards.sort_by(|a, b| label::natural_cmp(&a.label, &b.label));
+        Ok(())
     }
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stickere::memory_storage::MemoryStorage;
+
+    fn labels(cards: &[Card]) -> Vec<&str> {
+        cards.iter().map(|card| card.label.as_str()).collect()
+    }
+
+    #[test]
+    fn added_card_is_kept_in_label_order() {
+        let mut storage = MemoryStorage::new();
+        let collection_id = storage.add_collection("Euro 2024", 10).unwrap();
+        let mut cards = storage.get_cards_from_collection(collection_id).unwrap();
+        let mut history = History::new();
+
+        CardAdder::new().add_new_card("2a".to_string(), collection_id, &mut storage, &mut cards, &mut history).unwrap();
+
+        assert_eq!(labels(&cards[..4]), ["1", "2", "2a", "3"]);
+        assert_eq!(labels(&cards), labels(&storage.get_cards_from_collection(collection_id).unwrap()));
+
+        assert!(history.undo(&mut storage).unwrap());
+        assert_eq!(storage.get_cards_from_collection(collection_id).unwrap().len(), 10);
+    }
+
+    #[test]
+    fn existing_label_is_not_added() {
+        let mut storage = MemoryStorage::new();
+        let collection_id = storage.add_collection("Euro 2024", 3).unwrap();
+        let mut cards = storage.get_cards_from_collection(collection_id).unwrap();
+        let mut history = History::new();
+
+        let result = CardAdder::new().add_new_card("2".to_string(), collection_id, &mut storage, &mut cards, &mut history);
+
+        assert!(matches!(result, Err(StickereError::DuplicateLabel(label)) if label == "2"));
+        assert_eq!(labels(&cards), ["1", "2", "3"]);
+        assert!(!history.undo(&mut storage).unwrap());
+    }
+}
@@ -0,0 +1,218 @@
+use eframe::egui::{Color32, Id, Response, RichText, TextEdit, Ui};
+use stickere::card::Card;
+use stickere::card_details::{CardDetails, Rarity};
+use stickere::storage::Storage;
+use stickere::error::StickereError;
+use stickere::history::{Command, History};
+
+/// A change requested from the context menu of a card that affects the other cards of the collection,
+/// so it is carried out by the application rather than by the card itself.
+#[derive(Debug, PartialEq)]
+pub enum CardAction {
+    /// The card should be deleted.
+    Delete { card_id: u32 },
+    /// The label of the card should be changed.
+    Relabel { card_id: u32, label: String },
+}
+
+/// Renders a `Card` as a UI element.
+pub trait CardUi {
+    /// Renders the UI element corresponding to this card.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    /// * `history` - A mutable reference to the `History` recording the changes of this card.
+    /// * `action` - A mutable reference to an `Option<CardAction>`, set when a change is requested from the context menu.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of a database update triggered by this card, if any.
+    fn ui(&mut self, ui: &mut Ui, db_handler: &mut dyn Storage, history: &mut History,
+          action: &mut Option<CardAction>) -> Result<(), StickereError>;
+}
+
+impl CardUi for Card {
+    fn ui(&mut self, ui: &mut Ui, db_handler: &mut dyn Storage, history: &mut History,
+          action: &mut Option<CardAction>) -> Result<(), StickereError> {
+        let container_response = ui.vertical_centered(|ui| {
+            let mut responses = vec![];
+
+            let label = match self.details.rarity {
+                Rarity::Common => RichText::new(&self.label),
+                Rarity::Foil => RichText::new(&self.label).color(Color32::from_rgb(80, 160, 230)),
+                Rarity::Legend => RichText::new(&self.label).color(Color32::from_rgb(220, 170, 0)),
+            };
+            responses.push(ui.label(label));
+
+            let checkbox = ui.checkbox(&mut self.checked, "");
+            let result = if checkbox.changed() {
+                let result = db_handler.update_card(self.id, self.checked);
+                if result.is_ok() {
+                    history.push(Command::SetCollected { card_id: self.id, collected: self.checked });
+                }
+                result
+            } else {
+                Ok(())
+            };
+
+            responses.push(checkbox);
+
+            (responses, result)
+        });
+        let (responses, mut result) = container_response.inner;
+        for response in responses {
+            let response = if self.details.is_empty() {
+                response
+            } else {
+                response.on_hover_text(tooltip(&self.details))
+            };
+
+            let menu_result = add_context_menu(self, response, db_handler, history, action);
+            if result.is_ok() {
+                result = menu_result;
+            }
+        }
+        result
+    }
+}
+
+/// Adds a context menu to the card (right click menu).
+/// This menu handles the number of duplicates of a card, editing its details and label, and deleting it.
+/// Details are saved as soon as they are changed, while the label being typed is kept in the memory of the UI.
+///
+/// # Arguments
+///
+/// * `card` - A mutable reference to the `Card` the menu belongs to.
+/// * `response` - A `Response` object from the UI.
+/// * `db_handler` - A mutable reference to the `Storage` for database operations.
+/// * `history` - A mutable reference to the `History` recording the changes of the duplicates.
+/// * `action` - A mutable reference to an `Option<CardAction>`, set when the label is changed or the card deleted.
+///
+/// # Returns
+///
+/// * `Result<(), StickereError>` - The error of the database update triggered from the menu, if any.
+fn add_context_menu(card: &mut Card, response: Response, db_handler: &mut dyn Storage, history: &mut History,
+                    action: &mut Option<CardAction>) -> Result<(), StickereError> {
+    let mut result = Ok(());
+
+    response.context_menu(|ui| {
+        ui.vertical(|ui| {
+            ui.label(format!("Dubluri: {}", card.duplicates));
+
+            let before = card.duplicates;
+            if ui.button("Adauga dublura").clicked() {
+                card.duplicates += 1;
+                result = db_handler.update_card_duplicates(card);
+                ui.close_menu();
+            }
+
+            ui.separator();
+
+            if card.duplicates >= 1 && ui.button("Sterge dublura").clicked() {
+                card.duplicates -= 1;
+                result = db_handler.update_card_duplicates(card);
+                ui.close_menu();
+            }
+
+            if card.duplicates != before && result.is_ok() {
+                history.push(Command::SetDuplicates { card_id: card.id, before, after: card.duplicates });
+            }
+
+            ui.separator();
+
+            let mut changed = false;
+            changed |= ui.add(TextEdit::singleline(&mut card.details.name)
+                .hint_text("Nume")).changed();
+            changed |= ui.add(TextEdit::singleline(&mut card.details.category)
+                .hint_text("Echipa / categorie")).changed();
+
+            ui.horizontal(|ui| {
+                for rarity in Rarity::ALL {
+                    changed |= ui.radio_value(&mut card.details.rarity, rarity, rarity_name(rarity)).changed();
+                }
+            });
+
+            changed |= ui.add(TextEdit::multiline(&mut card.details.notes)
+                .hint_text("Note")
+                .desired_rows(2)).changed();
+
+            if changed {
+                result = db_handler.update_card_details(card);
+            }
+
+            ui.separator();
+
+            let label_id = Id::new(("CardLabel", card.id));
+            let mut new_label = ui.data_mut(|data| data.get_temp::<String>(label_id))
+                .unwrap_or_else(|| card.label.clone());
+
+            ui.horizontal(|ui| {
+                ui.add(TextEdit::singleline(&mut new_label)
+                    .hint_text("Eticheta")
+                    .desired_width(80.0));
+
+                let trimmed = new_label.trim();
+                if ui.button("Schimba eticheta").clicked() && !trimmed.is_empty() && trimmed != card.label {
+                    *action = Some(CardAction::Relabel { card_id: card.id, label: trimmed.to_string() });
+                    ui.close_menu();
+                }
+            });
+
+            if ui.button("Sterge stickerul").clicked() {
+                *action = Some(CardAction::Delete { card_id: card.id });
+                ui.close_menu();
+            }
+
+            if action.is_some() {
+                ui.data_mut(|data| data.remove::<String>(label_id));
+            } else {
+                ui.data_mut(|data| data.insert_temp(label_id, new_label));
+            }
+        });
+    });
+
+    result
+}
+
+/// Returns the name of a rarity shown in the UI.
+///
+/// # Arguments
+///
+/// * `rarity` - The `Rarity` to name.
+///
+/// # Returns
+///
+/// * `&'static str` - The display name of the rarity.
+fn rarity_name(rarity: Rarity) -> &'static str {
+    match rarity {
+        Rarity::Common => "Comun",
+        Rarity::Foil => "Foil",
+        Rarity::Legend => "Legenda",
+    }
+}
+
+/// Formats the filled in details of a card as one line each, for the hover tooltip of the card.
+///
+/// # Arguments
+///
+/// * `details` - A reference to the `CardDetails` of the card.
+///
+/// # Returns
+///
+/// * `String` - The details of the card.
+fn tooltip(details: &CardDetails) -> String {
+    let mut lines = vec![];
+    if !details.name.is_empty() {
+        lines.push(format!("Nume: {}", details.name));
+    }
+    if !details.category.is_empty() {
+        lines.push(format!("Echipa: {}", details.category));
+    }
+    lines.push(format!("Raritate: {}", rarity_name(details.rarity)));
+    if !details.notes.is_empty() {
+        lines.push(format!("Note: {}", details.notes));
+    }
+    lines.join("\n")
+}
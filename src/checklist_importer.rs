@@ -0,0 +1,122 @@
+use std::fs;
+use std::path::Path;
+use eframe::egui::{TextEdit, Ui};
+use stickere::checklist;
+use stickere::collection::Collection;
+use stickere::storage::Storage;
+use stickere::error::StickereError;
+use crate::error_text::ErrorText;
+use crate::ui_utils::UiUtils;
+
+/// A struct that represents the checklist importer UI component.
+/// It is used for creating a whole collection from a CSV or JSON checklist file in one step.
+pub struct ChecklistImporter {
+    file_path: String,
+    errors: Vec<String>,
+    show_errors: bool,
+}
+
+impl Default for ChecklistImporter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChecklistImporter {
+    /// Creates a new `ChecklistImporter` instance.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new instance of `ChecklistImporter`.
+    pub fn new() -> Self {
+        Self {
+            file_path: String::new(),
+            errors: vec![],
+            show_errors: false,
+        }
+    }
+
+    /// Renders the checklist importer UI.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    /// * `collections` - A mutable reference to a vector of `Collection` objects.
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of reading the file or adding the collection, if any.
+    pub fn ui(&mut self, ui: &mut Ui, collections: &mut Vec<Collection>,
+              db_handler: &mut dyn Storage) -> Result<(), StickereError> {
+        let mut result = Ok(());
+
+        if self.show_errors {
+            UiUtils::list_popup(ui.ctx(), &mut self.show_errors, "Lista nu a putut fi importata", &self.errors);
+        }
+
+        ui.label("Importa o lista (CSV sau JSON):");
+        ui.add_space(5.0);
+
+        ui.horizontal(|ui| {
+            ui.add(TextEdit::singleline(&mut self.file_path)
+                .hint_text("Calea fisierului")
+                .desired_width(90.0));
+
+            if ui.button("Importa").clicked() {
+                result = self.import(collections, db_handler);
+            }
+        });
+
+        ui.add_space(5.0);
+        result
+    }
+
+    /// Reads the checklist file and adds its collection.
+    /// Files ending in `.json` are read as JSON checklists, every other file as CSV.
+    /// The collection is named after the name declared in the file, or else after the file itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `collections` - A mutable reference to a vector of `Collection` objects.
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn import(&mut self, collections: &mut Vec<Collection>,
+              db_handler: &mut dyn Storage) -> Result<(), StickereError> {
+        let path = Path::new(self.file_path.trim());
+        if path.as_os_str().is_empty() {
+            return Ok(());
+        }
+
+        let text = fs::read_to_string(path)?;
+        let is_json = path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("json"));
+        let parsed = if is_json {
+            checklist::parse_json(&text)
+        } else {
+            checklist::parse_csv(&text)
+        };
+
+        match parsed {
+            Ok(checklist) => {
+                let name = checklist.name
+                    .filter(|name| !name.trim().is_empty())
+                    .or_else(|| path.file_stem().map(|stem| stem.to_string_lossy().into_owned()))
+                    .unwrap_or_default();
+
+                let collection_id = db_handler.import_collection(&name, &checklist.sections, &checklist.cards)?;
+                let collected = checklist.cards.iter().filter(|card| card.collected).count();
+                collections.push(Collection::new(collection_id, name, collected, checklist.cards.len()));
+                self.file_path.clear();
+            }
+            Err(errors) => {
+                self.errors = errors.iter().map(|error| error.error_text()).collect();
+                self.show_errors = true;
+            }
+        }
+
+        Ok(())
+    }
+}
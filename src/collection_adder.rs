@@ -1,6 +1,7 @@
 use eframe::egui::{Button, Color32, DragValue, FontId, RichText, TextEdit, Ui};
-use crate::collection::Collection;
-use crate::db::DbHandler;
+use stickere::collection::Collection;
+use stickere::storage::Storage;
+use stickere::error::StickereError;
 
 /// A struct that represents the collection adder UI component.
 /// It is used for adding new collection into the system
@@ -9,6 +10,12 @@ pub struct CollectionAdder {
     size: u32,
 }
 
+impl Default for CollectionAdder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl CollectionAdder {
     /// Creates a new `CollectionAdder` instance.
     ///
@@ -28,8 +35,15 @@ impl CollectionAdder {
     ///
     /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
     /// * `collections` - A mutable reference to a vector of `Collection` objects.
-    /// * `db_handler` - A mutable reference to the `DbHandler` for database operations.
-    pub fn ui(&mut self, ui: &mut Ui, collections: &mut Vec<Collection>, db_handler: &mut DbHandler) {
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of adding the new collection, if any.
+    pub fn ui(&mut self, ui: &mut Ui, collections: &mut Vec<Collection>,
+              db_handler: &mut dyn Storage) -> Result<(), StickereError> {
+        let mut result = Ok(());
+
         ui.label("Aduaga un nou catalog:");
         ui.add_space(5.0);
 
@@ -50,11 +64,12 @@ impl CollectionAdder {
             let button_response = ui.add_sized([40.0, 25.0], submit_button);
 
             if button_response.clicked() {
-                self.add_collection(collections, db_handler);
+                result = self.add_collection(collections, db_handler);
             }
         });
 
         ui.add_space(5.0);
+        result
     }
 
     /// Adds a new collection to the list of collections.
@@ -62,15 +77,21 @@ impl CollectionAdder {
     /// # Arguments
     ///
     /// * `collections` - A mutable reference to a vector of `Collection` objects.
-    /// * `db_handler` - A mutable reference to the `DbHandler` for database operations.
-    fn add_collection(&mut self, collections: &mut Vec<Collection>, db_handler: &mut DbHandler) {
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn add_collection(&mut self, collections: &mut Vec<Collection>,
+                      db_handler: &mut dyn Storage) -> Result<(), StickereError> {
         let name = self.collection_name.trim().to_string();
         if !name.is_empty() {
-            let collection_id = db_handler.add_collection(&name, self.size);
-            collections.push(Collection::new(collection_id, name.clone()));
+            let collection_id = db_handler.add_collection(&name, self.size)?;
+            collections.push(Collection::new(collection_id, name.clone(), 0, self.size as usize));
 
             self.collection_name.clear();
             self.size = 0;
         }
+        Ok(())
     }
 }
\ No newline at end of file
@@ -1,13 +1,35 @@
-use eframe::egui::{Align, Button, Color32, FontId, Layout, RichText, Ui};
-use crate::card::Card;
-use crate::collection::Collection;
-use crate::db::DbHandler;
+use std::fs;
+use eframe::egui::{Align, Button, Color32, DragValue, FontId, Layout, RichText, TextEdit, Ui};
+use stickere::card::Card;
+use stickere::checklist::ExportFormat;
+use stickere::collection::Collection;
+use stickere::storage::Storage;
+use stickere::error::StickereError;
+use stickere::history::{Command, History};
+use crate::ui_utils::UiUtils;
 
 /// A struct that represents the settings for a collection.
-/// It used for filtering and deleting collections
+/// It used for filtering, renaming, resizing, exporting and deleting collections.
+/// Deleting is confirmed and requested here, through `delete_requested`, and carried out by the application
+/// through `delete_collection`, once it has let go of the selected collection.
+/// A resize is reported through `resized`, so the application can clear the changes recorded for undo.
 pub struct CollectionSettings {
     pub show_collected: bool,
     pub show_not_collected: bool,
+    pub delete_requested: bool,
+    pub resized: bool,
+    confirm_delete: bool,
+    name: String,
+    size: u32,
+    edited_collection_id: Option<u32>,
+    export_path: String,
+    export_message: Option<String>,
+}
+
+impl Default for CollectionSettings {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl CollectionSettings {
@@ -20,6 +42,14 @@ impl CollectionSettings {
         Self {
             show_collected: true,
             show_not_collected: true,
+            delete_requested: false,
+            resized: false,
+            confirm_delete: false,
+            name: String::new(),
+            size: 0,
+            edited_collection_id: None,
+            export_path: String::new(),
+            export_message: None,
         }
     }
 
@@ -28,15 +58,28 @@ impl CollectionSettings {
     /// # Arguments
     ///
     /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
-    /// * `db_handler` - A reference to the `DbHandler` for database operations.
-    /// * `collections` - A mutable reference to a vector of `Collection` objects.
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    /// * `collections` - A mutable reference to a vector of `Collection` objects, reloaded when the selected one is edited.
     /// * `cards` - A mutable reference to a vector of `Card` objects of the currently selected collection.
-    /// * `selected_collection` - A mutable reference to an `Option<u32>` representing the selected collection's ID.
+    /// * `selected_collection` - An `Option<u32>` representing the selected collection's ID.
     /// * `selected_collection_name` - A mutable reference to an `Option<String>` representing the selected collection's name.
-    pub fn ui(&mut self, ui: &mut Ui, db_handler: &DbHandler, collections: &mut Vec<Collection>,
-              cards: &mut Vec<Card>,
-              selected_collection: &mut Option<u32>,
-              selected_collection_name: &mut Option<String>) {
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of editing or exporting the selected collection, if any.
+    pub fn ui(&mut self, ui: &mut Ui, db_handler: &mut dyn Storage, collections: &mut Vec<Collection>,
+              cards: &mut Vec<Card>, selected_collection: Option<u32>,
+              selected_collection_name: &mut Option<String>) -> Result<(), StickereError> {
+        let mut result = Ok(());
+
+        if self.confirm_delete {
+            let prompt = format!("Muti catalogul \"{}\" in cosul de gunoi?",
+                                 selected_collection_name.as_deref().unwrap_or_default());
+            if UiUtils::confirm(ui.ctx(), &mut self.confirm_delete, "Sterge catalogul", &prompt) {
+                self.delete_requested = true;
+            }
+        }
+
         ui.label("Filtreaza stickere:");
         ui.add_space(5.0);
 
@@ -54,18 +97,201 @@ impl CollectionSettings {
                 )
                     .fill(Color32::from_rgb(200, 0, 0));
 
-                if ui.add_sized([30.0, 30.0], delete_button).clicked() {
-                    if let &mut Some(selected_collection_id) = selected_collection {
-                        db_handler.delete_collection(selected_collection_id);
-                        *selected_collection = None;
-                        *selected_collection_name = None;
-                        cards.clear();
-                        collections.retain(|collection| collection.id != selected_collection_id);
-                    }
+                if ui.add_sized([30.0, 30.0], delete_button).clicked() && selected_collection.is_some() {
+                    self.confirm_delete = true;
                 }
 
                 ui.label("Sterge catalogul");
             });
         });
+
+        if let Some(selected_collection_id) = selected_collection {
+            result = self.edit_ui(ui, db_handler, collections, cards, selected_collection_id, selected_collection_name);
+
+            ui.horizontal(|ui| {
+                ui.add(TextEdit::singleline(&mut self.export_path)
+                    .hint_text("Fisierul exportului sau lasa gol")
+                    .desired_width(200.0));
+
+                for format in [ExportFormat::Csv, ExportFormat::Json] {
+                    let button_text = format!("Exporta {}", format.extension().to_uppercase());
+                    if ui.button(button_text).clicked() {
+                        result = self.export(db_handler, selected_collection_id, selected_collection_name, format);
+                    }
+                }
+
+                if let Some(message) = &self.export_message {
+                    ui.label(message);
+                }
+            });
+        }
+
+        result
+    }
+
+    /// Moves a collection to the trash, records the deletion so it can be undone and removes it from the listed collections.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    /// * `history` - A mutable reference to the `History` recording the deletion.
+    /// * `collections` - A mutable reference to a vector of the listed `Collection` objects.
+    /// * `collection_id` - A `u32` representing the identifier of the collection to delete.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of deleting the collection, if any.
+    pub fn delete_collection(&self, db_handler: &mut dyn Storage, history: &mut History, collections: &mut Vec<Collection>,
+                             collection_id: u32) -> Result<(), StickereError> {
+        db_handler.delete_collection(collection_id)?;
+        history.push(Command::DeleteCollection { collection_id });
+        collections.retain(|collection| collection.id != collection_id);
+        Ok(())
+    }
+
+    /// Renders the fields for renaming and resizing the selected collection and applies the changes.
+    /// The fields are filled in with the current name and size whenever another collection is selected.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    /// * `collections` - A mutable reference to a vector of `Collection` objects.
+    /// * `cards` - A mutable reference to a vector of `Card` objects of the currently selected collection.
+    /// * `collection_id` - A `u32` representing the identifier of the selected collection.
+    /// * `collection_name` - A mutable reference to an `Option<String>` representing the selected collection's name.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of renaming or resizing the collection, if any.
+    fn edit_ui(&mut self, ui: &mut Ui, db_handler: &mut dyn Storage, collections: &mut Vec<Collection>,
+               cards: &mut Vec<Card>, collection_id: u32,
+               collection_name: &mut Option<String>) -> Result<(), StickereError> {
+        if self.edited_collection_id != Some(collection_id) {
+            self.name = collection_name.clone().unwrap_or_default();
+            self.size = cards.len() as u32;
+            self.edited_collection_id = Some(collection_id);
+        }
+
+        let mut result = Ok(());
+
+        ui.horizontal(|ui| {
+            ui.add(TextEdit::singleline(&mut self.name)
+                .hint_text("Numele catalogului")
+                .desired_width(150.0));
+
+            let name = self.name.trim();
+            if ui.button("Redenumeste").clicked() && !name.is_empty() {
+                result = db_handler.rename_collection(collection_id, name)
+                    .and_then(|_| {
+                        *collection_name = Some(name.to_string());
+                        *collections = db_handler.get_collections()?;
+                        Ok(())
+                    });
+            }
+
+            ui.label("Marime:");
+            ui.add(DragValue::new(&mut self.size));
+
+            if ui.button("Redimensioneaza").clicked() && self.size as usize != cards.len() {
+                result = db_handler.resize_collection(collection_id, self.size)
+                    .and_then(|_| {
+                        *cards = db_handler.get_cards_from_collection(collection_id)?;
+                        *collections = db_handler.get_collections()?;
+                        Ok(())
+                    });
+                match result {
+                    Ok(()) => self.resized = true,
+                    Err(_) => self.size = cards.len() as u32,
+                }
+            }
+        });
+
+        result
     }
-}
\ No newline at end of file
+
+    /// Exports the selected collection to the chosen file.
+    /// If no file is given, it is written to the current working directory, named after the collection
+    /// with the characters that cannot appear in a file name replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_handler` - A reference to the `Storage` for database operations.
+    /// * `collection_id` - A `u32` representing the identifier of the selected collection.
+    /// * `collection_name` - A reference to an `Option<String>` representing the selected collection's name.
+    /// * `format` - The `ExportFormat` to write the collection in.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn export(&mut self, db_handler: &dyn Storage, collection_id: u32, collection_name: &Option<String>,
+              format: ExportFormat) -> Result<(), StickereError> {
+        let path = match self.export_path.trim() {
+            "" => format!("{}.{}", file_name(collection_name.as_deref().unwrap_or_default()), format.extension()),
+            path => path.to_string(),
+        };
+
+        let contents = db_handler.export_collection(collection_id, format)?;
+        fs::write(&path, contents)?;
+
+        self.export_message = Some(format!("Exportat in {path}"));
+        Ok(())
+    }
+}
+
+/// Turns a collection name into a file name, replacing path separators and the characters reserved on Windows
+/// and trimming the dots and spaces at its ends, so the name cannot point outside the current directory.
+///
+/// # Arguments
+///
+/// * `name` - A string slice holding the collection name.
+///
+/// # Returns
+///
+/// * `String` - The file name, without extension, or `catalog` if nothing is left of the name.
+fn file_name(name: &str) -> String {
+    let file_name: String = name.chars()
+        .map(|c| if c.is_control() || "/\\:*?\"<>|".contains(c) { '_' } else { c })
+        .collect();
+
+    match file_name.trim_matches(['.', ' ']) {
+        "" => "catalog".to_string(),
+        file_name => file_name.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use stickere::memory_storage::MemoryStorage;
+
+    #[test]
+    fn deleted_collection_goes_to_the_trash_until_undone() {
+        let mut storage = MemoryStorage::new();
+        let kept_id = storage.add_collection("Euro 2024", 2).unwrap();
+        let deleted_id = storage.add_collection("Euro 2020", 2).unwrap();
+        let mut collections = storage.get_collections().unwrap();
+        let mut history = History::new();
+
+        CollectionSettings::new().delete_collection(&mut storage, &mut history, &mut collections, deleted_id).unwrap();
+
+        assert_eq!(collections.iter().map(|collection| collection.id).collect::<Vec<_>>(), [kept_id]);
+        assert_eq!(storage.get_collections().unwrap(), collections);
+        assert_eq!(storage.get_deleted_collections().unwrap()[0].id, deleted_id);
+
+        assert!(history.undo(&mut storage).unwrap());
+        assert_eq!(storage.get_collections().unwrap().len(), 2);
+        assert!(storage.get_deleted_collections().unwrap().is_empty());
+        assert_eq!(storage.get_cards_from_collection(deleted_id).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn collection_names_become_safe_file_names() {
+        assert_eq!(file_name("Euro 2024"), "Euro 2024");
+        assert_eq!(file_name("Euro 24/25"), "Euro 24_25");
+        assert_eq!(file_name("../x"), "_x");
+        assert_eq!(file_name("C:\\Stickere\\\"Liga\"?"), "C__Stickere__Liga__");
+        assert_eq!(file_name(".."), "catalog");
+        assert_eq!(file_name(""), "catalog");
+    }
+}
@@ -0,0 +1,39 @@
+use eframe::egui::{CollapsingHeader, Grid, ProgressBar, Ui};
+use stickere::collection_stats::{self, CollectionStats};
+
+/// Renders the `CollectionStats` of the selected collection as a panel.
+pub trait CollectionStatsUi {
+    /// Renders the statistics panel.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    fn ui(&self, ui: &mut Ui);
+}
+
+impl CollectionStatsUi for CollectionStats {
+    fn ui(&self, ui: &mut Ui) {
+        CollapsingHeader::new("Statistici")
+            .id_source("CollectionStats")
+            .show(ui, |ui| {
+                ui.add(ProgressBar::new(self.completion())
+                    .text(format!("{}/{} ({:.1}%)", self.collected, self.total, self.completion() * 100.0)));
+                ui.label(format!("Lipsa: {}, dubluri: {}", self.missing(), self.duplicates));
+
+                if self.sections.is_empty() {
+                    return;
+                }
+
+                ui.add_space(5.0);
+                Grid::new("SectionStats").num_columns(2).striped(true).show(ui, |ui| {
+                    for (name, collected, total) in &self.sections {
+                        ui.label(name.as_deref().unwrap_or("Fara sectiune"));
+                        ui.add(ProgressBar::new(collection_stats::fraction(*collected, *total))
+                            .desired_width(150.0)
+                            .text(format!("{collected}/{total}")));
+                        ui.end_row();
+                    }
+                });
+            });
+    }
+}
@@ -0,0 +1,55 @@
+use eframe::egui::{ProgressBar, Ui};
+use stickere::card::Card;
+use stickere::collection::Collection;
+use stickere::collection_stats;
+use stickere::storage::Storage;
+use stickere::error::StickereError;
+use stickere::section::Section;
+
+/// Renders a `Collection` as a UI element.
+pub trait CollectionUi {
+    /// Renders the collection's button, followed by its progress bar, and updates the selected collection, its name, cards and sections.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    /// * `cards` - A mutable reference to a vector of `Card` objects.
+    /// * `sections` - A mutable reference to a vector of `Section` objects.
+    /// * `db_handler` - A reference to the `Storage` for database operations.
+    /// * `selected_collection` - A mutable reference to an `Option<u32>` representing the selected collection's ID.
+    /// * `selected_collection_name` - A mutable reference to an `Option<String>` representing the selected collection's name.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of loading the selected collection, if any.
+    fn ui(&self, ui: &mut Ui, cards: &mut Vec<Card>, sections: &mut Vec<Section>, db_handler: &dyn Storage,
+          selected_collection: &mut Option<u32>,
+          selected_collection_name: &mut Option<String>) -> Result<(), StickereError>;
+}
+
+impl CollectionUi for Collection {
+    fn ui(&self, ui: &mut Ui, cards: &mut Vec<Card>, sections: &mut Vec<Section>, db_handler: &dyn Storage,
+          selected_collection: &mut Option<u32>,
+          selected_collection_name: &mut Option<String>) -> Result<(), StickereError> {
+        let clicked = ui.horizontal(|ui| {
+            let clicked = ui.button(self.name()).clicked();
+
+            let (collected, total) = self.progress();
+            let progress = collection_stats::fraction(collected, total);
+            ui.add(ProgressBar::new(progress)
+                .desired_width(ui.available_width().max(40.0))
+                .text(format!("{:.0}%", progress * 100.0)))
+                .on_hover_text(format!("{collected}/{total}"));
+
+            clicked
+        }).inner;
+
+        if clicked {
+            *cards = db_handler.get_cards_from_collection(self.id)?;
+            *sections = db_handler.get_sections_from_collection(self.id)?;
+            *selected_collection = Some(self.id);
+            *selected_collection_name = Some(db_handler.get_collection_name(self.id)?)
+        }
+        Ok(())
+    }
+}
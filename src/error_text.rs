@@ -0,0 +1,66 @@
+use stickere::checklist::{ChecklistError, ChecklistProblem};
+use stickere::error::StickereError;
+use stickere::trade_list::TradeListError;
+
+/// Describes a problem reported by the library in the language of the UI.
+/// The library describes its problems in English, for the command-line interface.
+pub trait ErrorText {
+    /// Returns the description of the problem shown to the user.
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The description of the problem.
+    fn error_text(&self) -> String;
+}
+
+impl ErrorText for StickereError {
+    fn error_text(&self) -> String {
+        match self {
+            StickereError::Io(err) => format!("Eroare la accesarea fisierelor: {err}"),
+            StickereError::Database(err) => format!("Eroare a bazei de date: {err}"),
+            StickereError::UnsupportedSchemaVersion(version) => {
+                format!("Baza de date are versiunea {version}, mai noua decat poate folosi aplicatia")
+            }
+            StickereError::Serialization(message) => format!("Eroare la export: {message}"),
+            StickereError::CardNotFound(label) => format!("Stickerul {label} nu exista in acest catalog"),
+            StickereError::NoDuplicateToGive(label) => format!("Stickerul {label} nu are nicio dublura de dat"),
+            StickereError::CardNotEmpty(label) => {
+                format!("Stickerul {label} este colectat sau are dubluri ori detalii si nu poate fi sters")
+            }
+            StickereError::DuplicateLabel(label) => format!("Stickerul {label} exista deja in acest catalog"),
+            StickereError::NoDuplicateToRemove(label) => format!("Stickerul {label} nu are nicio dublura de sters"),
+            StickereError::CollectionNotFound(name) => format!("Catalogul {name} nu exista"),
+            StickereError::InvalidProfileName(name) => {
+                format!("Numele de profil \"{name}\" poate contine doar litere, cifre, spatii, - si _")
+            }
+            StickereError::BackupNotFound(path) => format!("Backup-ul {path} nu exista"),
+            StickereError::InvalidArgument(message) => format!("Argument invalid: {message}"),
+        }
+    }
+}
+
+impl ErrorText for ChecklistError {
+    fn error_text(&self) -> String {
+        let problem = match &self.problem {
+            ChecklistProblem::MissingLabelColumn => "coloana \"label\" lipseste".to_string(),
+            ChecklistProblem::MissingLabel => "eticheta lipseste".to_string(),
+            ChecklistProblem::RepeatedLabel(label) => format!("eticheta \"{label}\" apare de mai multe ori"),
+            ChecklistProblem::UnknownRarity(rarity) => {
+                format!("raritate necunoscuta \"{rarity}\" (common, foil sau legend)")
+            }
+            ChecklistProblem::Malformed(message) => message.clone(),
+        };
+        format!("Linia {}: {problem}", self.line)
+    }
+}
+
+impl ErrorText for TradeListError {
+    fn error_text(&self) -> String {
+        match self {
+            TradeListError::UnknownKeyword(keyword) => format!("cuvant cheie necunoscut \"{keyword}\""),
+            TradeListError::InvalidDuplicateCount(entry) => format!("numar de dubluri invalid in \"{entry}\""),
+            TradeListError::MissingKeyword(entry) => format!("lipseste \"Need:\" sau \"Got:\" inainte de \"{entry}\""),
+            TradeListError::InvalidRange(entry) => format!("interval invalid \"{entry}\""),
+        }
+    }
+}
@@ -1,25 +1,57 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
-mod db;
 mod ui;
-mod card;
-mod collection;
+mod card_ui;
+mod collection_ui;
 mod collection_adder;
 mod collection_settings;
+mod collection_stats_ui;
 mod card_adder;
 mod ui_utils;
+mod error_text;
+mod section_adder;
+mod checklist_importer;
+mod trade_panel;
+mod trash;
+mod pack_opener;
+mod profile_menu;
+mod backup_dialog;
+mod startup_error;
+use std::env;
+use std::path::PathBuf;
+use clap::Parser;
 use eframe::egui::{pos2, vec2, ViewportBuilder};
-use crate::db::DbHandler;
+use stickere::profile::{self, DbLocation, Profiles};
+use crate::profile_menu::ProfileMenu;
+use crate::startup_error::StartupError;
 use crate::ui::App;
 
+/// The command line of the application, choosing the sticker database to open.
+#[derive(Parser)]
+#[command(name = "Sickere", version)]
+struct Args {
+    /// The sticker database file, instead of the database of a profile.
+    #[arg(long, conflicts_with = "profile")]
+    db: Option<PathBuf>,
+
+    /// The profile whose database is opened, `default` if neither this nor the `STICKERE_DB` environment variable is set.
+    #[arg(long)]
+    profile: Option<String>,
+}
+
 /// The main function initializes the database handler, sets up the native options for the eframe application,
 /// and runs the application with the specified settings.
+/// If the database cannot be opened, a window reporting the problem is shown instead.
 ///
 /// # Returns
 /// * `eframe::Result` - The result of running the eframe application.
 fn main() -> eframe::Result {
+    let args = Args::parse();
 
-    // Initialize the database handler with the specified database file.
-    let db = DbHandler::new("db/stick.db");
+    // Initialize the database handler with the database chosen on the command line, by the environment,
+    // or with the default profile in the platform data directory.
+    let profiles = Profiles::in_data_dir();
+    let location = DbLocation::resolve(args.db, args.profile, env::var_os(profile::DB_PATH_VAR));
+    let opened = location.open(&profiles);
 
     // Set up the native options for the eframe application, including viewport size and position.
     let native_options = eframe::NativeOptions {
@@ -32,6 +64,12 @@ fn main() -> eframe::Result {
     // Run the eframe application with the specified title, native options, and application instance.
     eframe::run_native("Manager de stickere",
                        native_options,
-                       Box::new(|cc| Ok(Box::new(App::new(cc, db)))))
+                       Box::new(|cc| {
+                           let app: Box<dyn eframe::App> = match opened {
+                               Ok(db) => Box::new(App::new(cc, Box::new(db), ProfileMenu::new(profiles, location))),
+                               Err(err) => Box::new(StartupError::new(&err)),
+                           };
+                           Ok(app)
+                       }))
 }
 
@@ -0,0 +1,225 @@
+use std::collections::HashSet;
+use eframe::egui::{CollapsingHeader, DragValue, Key, TextEdit, Ui};
+use stickere::card::Card;
+use stickere::storage::Storage;
+use stickere::error::StickereError;
+use stickere::history::History;
+use stickere::estimate::{CompletionEstimate, Cost};
+use stickere::pack::{self, Pack, PackStats};
+use stickere::trade_list;
+
+/// A struct that represents the pack opening UI component.
+/// The numbers of the stickers in a freshly opened pack are typed in one go, each sticker being
+/// marked as collected or added as a duplicate. Every opened pack is kept in the pack history
+/// of the collection, together with statistics about the packs bought and an estimate of
+/// the packs still needed to finish the album.
+pub struct PackOpener {
+    pack: String,
+    pack_price: f64,
+    stickers_per_pack: u32,
+    ordered: usize,
+    sticker_price: f64,
+    packs: Vec<Pack>,
+    packs_collection_id: Option<u32>,
+}
+
+impl Default for PackOpener {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PackOpener {
+    /// Creates a new `PackOpener` instance.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new instance of `PackOpener`.
+    pub fn new() -> Self {
+        Self {
+            pack: String::new(),
+            pack_price: 5.0,
+            stickers_per_pack: 5,
+            ordered: 0,
+            sticker_price: 1.0,
+            packs: vec![],
+            packs_collection_id: None,
+        }
+    }
+
+    /// Renders the pack opening UI and opens the typed pack when submitted.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    /// * `cards` - A mutable reference to a vector of the `Card` objects of the selected collection.
+    /// * `collection_id` - A `u32` representing the identifier of the current selected collection.
+    /// * `history` - A mutable reference to the `History`, cleared when a pack is opened.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of loading the history or opening the pack, if any.
+    pub fn ui(&mut self, ui: &mut Ui, db_handler: &mut dyn Storage, cards: &mut Vec<Card>,
+              collection_id: u32, history: &mut History) -> Result<(), StickereError> {
+        if self.packs_collection_id != Some(collection_id) {
+            self.packs = db_handler.get_packs_from_collection(collection_id)?;
+            self.packs_collection_id = Some(collection_id);
+        }
+
+        let mut result = Ok(());
+
+        CollapsingHeader::new("Deschide pachet")
+            .id_source("PackOpener")
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    let response = ui.add(TextEdit::singleline(&mut self.pack)
+                        .hint_text("Numerele din pachet, ex. 3 17, 44")
+                        .desired_width(250.0));
+                    let submitted = response.lost_focus() && ui.input(|input| input.key_pressed(Key::Enter));
+
+                    if (ui.button("Deschide").clicked() || submitted) && !self.pack.trim().is_empty() {
+                        result = self.open_pack(db_handler, cards, collection_id, history);
+                    }
+                });
+
+                if let Some(last) = self.packs.last() {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(format!("Ultimul pachet: {} noi ({}), {} dubluri ({})",
+                                         last.summary.new.len(),
+                                         trade_list::format_labels(&last.summary.new),
+                                         last.summary.duplicates.len(),
+                                         trade_list::format_labels(&last.summary.duplicates)));
+                    });
+                }
+
+                ui.add_space(5.0);
+                self.stats_ui(ui);
+                self.estimate_ui(ui, cards);
+                self.history_ui(ui);
+            });
+
+        result
+    }
+
+    /// Marks the typed stickers as received and reloads the cards of the collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    /// * `cards` - A mutable reference to a vector of the `Card` objects of the selected collection.
+    /// * `collection_id` - A `u32` representing the identifier of the current selected collection.
+    /// * `history` - A mutable reference to the `History`, cleared since the changes it recorded
+    ///   may no longer apply to the received cards.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of opening the pack, if any.
+    fn open_pack(&mut self, db_handler: &mut dyn Storage, cards: &mut Vec<Card>,
+                 collection_id: u32, history: &mut History) -> Result<(), StickereError> {
+        let labels: HashSet<&str> = cards.iter().map(|card| card.label.as_str()).collect();
+        let pack = pack::parse_pack(&self.pack, &labels);
+
+        db_handler.open_pack(collection_id, &pack)?;
+        history.clear();
+        *cards = db_handler.get_cards_from_collection(collection_id)?;
+        self.packs = db_handler.get_packs_from_collection(collection_id)?;
+        self.pack.clear();
+
+        Ok(())
+    }
+
+    /// Renders the statistics of the packs bought for the selected collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    fn stats_ui(&mut self, ui: &mut Ui) {
+        let stats = PackStats::from_packs(&self.packs);
+
+        ui.horizontal(|ui| {
+            ui.label("Pret pachet:");
+            ui.add(DragValue::new(&mut self.pack_price).speed(0.1).range(0.0..=f64::MAX).max_decimals(2));
+        });
+
+        ui.label(format!("Pachete cumparate: {} (cost total {:.2})", stats.packs, stats.packs as f64 * self.pack_price));
+        ui.label(format!("Stickere noi: {}, dubluri: {}, rata dublurilor: {}",
+                         stats.new,
+                         stats.duplicates,
+                         stats.duplicate_rate().map_or("-".to_string(), |rate| format!("{:.0}%", rate * 100.0))));
+        ui.label(format!("Cost per sticker nou: {}",
+                         stats.cost_per_new(self.pack_price).map_or("-".to_string(), |cost| format!("{cost:.2}"))));
+    }
+
+    /// Renders the estimate of the packs and money still needed to finish the selected collection,
+    /// by buying packs only, after trading the duplicates, or by ordering the last missing stickers.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    /// * `cards` - A slice of the `Card` objects of the selected collection.
+    fn estimate_ui(&mut self, ui: &mut Ui, cards: &[Card]) {
+        let missing = cards.iter().filter(|card| !card.checked).count();
+        let duplicates: u32 = cards.iter().map(|card| card.duplicates).sum();
+
+        ui.add_space(5.0);
+        ui.horizontal(|ui| {
+            ui.label("Stickere per pachet:");
+            ui.add(DragValue::new(&mut self.stickers_per_pack).range(1..=100));
+        });
+        ui.horizontal(|ui| {
+            ui.label("Comanda ultimele");
+            ui.add(DragValue::new(&mut self.ordered).range(0..=missing));
+            ui.label("stickere la pretul de");
+            ui.add(DragValue::new(&mut self.sticker_price).speed(0.1).range(0.0..=f64::MAX).max_decimals(2));
+        });
+
+        let estimate = CompletionEstimate {
+            total: cards.len(),
+            missing,
+            stickers_per_pack: self.stickers_per_pack,
+            pack_price: self.pack_price,
+        };
+        let buying = estimate.buying();
+        let describe = |cost: &Cost| format!("~{:.0} pachete, ~{:.2}", cost.packs.ceil(), cost.money);
+        let savings = |cost: &Cost| format!("{} (economisesti ~{:.2})", describe(cost), buying.money - cost.money);
+
+        ui.label(format!("Pana la completare, doar din pachete: {}", describe(&buying)));
+        ui.label(format!("Dupa schimbul celor {duplicates} dubluri: {}", savings(&estimate.trading(duplicates as usize))));
+        ui.label(format!("Comandand ultimele {}: {}",
+                         self.ordered.min(missing),
+                         savings(&estimate.ordering(self.ordered, self.sticker_price))));
+    }
+
+    /// Renders the pack history of the selected collection, with the duplicate rate of every pack
+    /// and the duplicate rate of all the packs opened up to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    fn history_ui(&self, ui: &mut Ui) {
+        CollapsingHeader::new(format!("Istoric pachete ({})", self.packs.len()))
+            .id_source("PackHistory")
+            .show(ui, |ui| {
+                let mut cumulative = PackStats::default();
+                let cumulative_rates: Vec<f64> = self.packs.iter().map(|pack| {
+                    cumulative.new += pack.summary.new.len() as u32;
+                    cumulative.duplicates += pack.summary.duplicates.len() as u32;
+                    cumulative.duplicate_rate().unwrap_or(0.0)
+                }).collect();
+
+                for (pack, cumulative_rate) in self.packs.iter().zip(cumulative_rates).rev() {
+                    let rate = PackStats::from_packs([pack]).duplicate_rate().unwrap_or(0.0);
+
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(format!("{} - noi {}, dubluri {} ({:.0}%, cumulat {:.0}%)",
+                                         pack.opened_at,
+                                         trade_list::format_labels(&pack.summary.new),
+                                         trade_list::format_labels(&pack.summary.duplicates),
+                                         rate * 100.0,
+                                         cumulative_rate * 100.0));
+                    });
+                }
+            });
+    }
+}
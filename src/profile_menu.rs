@@ -0,0 +1,133 @@
+use eframe::egui::{TextEdit, Ui};
+use stickere::db::DbHandler;
+use stickere::error::StickereError;
+use stickere::profile::{DbLocation, Profiles, DEFAULT_PROFILE};
+use crate::ui_utils::UiUtils;
+
+/// A struct that represents the profile menu UI component.
+/// Every profile, e.g. one per family member, has its own database, and the menu switches between them
+/// or creates new ones. Switching is carried out by the application, which starts over with the opened database.
+/// It also reports, once, a database file used before profiles existed that was not copied to the default profile.
+pub struct ProfileMenu {
+    profiles: Profiles,
+    location: DbLocation,
+    names: Option<Vec<String>>,
+    new_profile: String,
+    legacy_checked: bool,
+    legacy_notice: String,
+    show_legacy_notice: bool,
+}
+
+impl ProfileMenu {
+    /// Creates a new `ProfileMenu` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `profiles` - The `Profiles` to choose from.
+    /// * `location` - The `DbLocation` of the open database.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new instance of `ProfileMenu`.
+    pub fn new(profiles: Profiles, location: DbLocation) -> Self {
+        Self {
+            profiles,
+            location,
+            names: None,
+            new_profile: String::new(),
+            legacy_checked: false,
+            legacy_notice: String::new(),
+            show_legacy_notice: false,
+        }
+    }
+
+    /// Renders the menu with the profiles and opens the profile chosen or created from it.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<DbHandler>, StickereError>` - The database of the profile switched to, if any,
+    ///   or the error of listing or opening the profiles.
+    pub fn ui(&mut self, ui: &mut Ui) -> Result<Option<DbHandler>, StickereError> {
+        let mut result = Ok(None);
+
+        if !self.legacy_checked {
+            self.legacy_checked = true;
+            if let Some(path) = self.profiles.take_skipped_legacy_db()? {
+                self.legacy_notice = format!("Baza de date veche {path} nu a fost copiata in profilul {DEFAULT_PROFILE}, \
+                                              care avea deja o baza de date. Porneste aplicatia cu --db \"{path}\" \
+                                              ca sa o deschizi.", path = path.display());
+                self.show_legacy_notice = true;
+            }
+        }
+        if self.show_legacy_notice {
+            UiUtils::popup(ui.ctx(), &mut self.show_legacy_notice, "Baza de date veche", &self.legacy_notice);
+        }
+
+        let title = match &self.location {
+            DbLocation::Profile(name) => format!("Profil: {name}"),
+            DbLocation::File(path) => format!("Fisier: {}", path.display()),
+        };
+
+        let response = ui.menu_button(title, |ui| {
+            if self.names.is_none() {
+                match self.profiles.list() {
+                    Ok(names) => self.names = Some(names),
+                    Err(err) => result = Err(err),
+                }
+            }
+
+            let mut chosen = None;
+            for name in self.names.iter().flatten() {
+                let current = self.location == DbLocation::Profile(name.clone());
+                if ui.radio(current, name).clicked() && !current {
+                    chosen = Some(name.clone());
+                }
+            }
+
+            ui.separator();
+
+            ui.horizontal(|ui| {
+                ui.add(TextEdit::singleline(&mut self.new_profile)
+                    .hint_text("Nume profil")
+                    .desired_width(120.0));
+
+                let name = self.new_profile.trim();
+                if ui.button("Profil nou").clicked() && !name.is_empty() {
+                    chosen = Some(name.to_string());
+                }
+            });
+
+            if let Some(name) = chosen {
+                result = self.switch(&name).map(Some);
+                ui.close_menu();
+            }
+        });
+
+        // The profiles are listed again whenever the menu is opened, to show profiles created elsewhere.
+        if response.inner.is_none() {
+            self.names = None;
+        }
+
+        result
+    }
+
+    /// Opens the database of a profile, creating the profile if it is new, and makes it the current profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A string slice holding the name of the profile.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<DbHandler, StickereError>` - The database of the profile.
+    fn switch(&mut self, name: &str) -> Result<DbHandler, StickereError> {
+        let db_handler = self.profiles.open(name)?;
+        self.location = DbLocation::Profile(name.to_string());
+        self.new_profile.clear();
+        Ok(db_handler)
+    }
+}
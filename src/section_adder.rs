@@ -0,0 +1,110 @@
+use eframe::egui::{TextEdit, Ui};
+use stickere::card::Card;
+use stickere::storage::Storage;
+use stickere::error::StickereError;
+use stickere::section::Section;
+
+/// A struct that represents the section adder UI component.
+/// It is used for adding a new section to a collection and moving a range of its cards into it.
+pub struct SectionAdder {
+    section_name: String,
+    first_label: String,
+    last_label: String,
+}
+
+impl Default for SectionAdder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SectionAdder {
+    /// Creates a new `SectionAdder` instance.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new instance of `SectionAdder`.
+    pub fn new() -> Self {
+        Self {
+            section_name: String::new(),
+            first_label: String::new(),
+            last_label: String::new(),
+        }
+    }
+
+    /// Renders the UI of the SectionAdder element.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    /// * `sections` - A mutable reference to a vector of the `Section` objects of the selected collection.
+    /// * `cards` - A mutable reference to a vector of the `Card` objects of the selected collection.
+    /// * `collection_id` - A `u32` representing the identifier of the current selected collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of adding the new section, if any.
+    pub fn ui(&mut self, ui: &mut Ui, db_handler: &mut dyn Storage, sections: &mut Vec<Section>,
+              cards: &mut Vec<Card>, collection_id: u32) -> Result<(), StickereError> {
+        let mut result = Ok(());
+
+        ui.horizontal(|ui| {
+            ui.add(TextEdit::singleline(&mut self.section_name)
+                .hint_text("Numele sectiunii")
+                .desired_width(150.0));
+            ui.add(TextEdit::singleline(&mut self.first_label)
+                .hint_text("De la")
+                .desired_width(60.0));
+            ui.add(TextEdit::singleline(&mut self.last_label)
+                .hint_text("Pana la")
+                .desired_width(60.0));
+
+            if ui.button("Adauga Sectiune").clicked() {
+                result = self.add_section(db_handler, sections, cards, collection_id);
+            }
+        });
+
+        result
+    }
+
+    /// Adds a new section to the selected collection and moves the cards between
+    /// the first and last label, inclusive, into it.
+    /// If only the first label is given, only that card is moved.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    /// * `sections` - A mutable reference to a vector of `Section` objects.
+    /// * `cards` - A mutable reference to a vector of `Card` objects.
+    /// * `collection_id` - A `u32` representing the identifier of the selected collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn add_section(&mut self, db_handler: &mut dyn Storage, sections: &mut Vec<Section>,
+                   cards: &mut Vec<Card>, collection_id: u32) -> Result<(), StickereError> {
+        let name = self.section_name.trim().to_string();
+        if name.is_empty() {
+            return Ok(());
+        }
+
+        let section_id = db_handler.add_section(collection_id, &name)?;
+        sections.push(Section::new(section_id, name));
+
+        let first_label = self.first_label.trim();
+        let last_label = match self.last_label.trim() {
+            "" => first_label,
+            last_label => last_label,
+        };
+        if !first_label.is_empty() {
+            db_handler.assign_cards_to_section(collection_id, section_id, first_label, last_label)?;
+            *cards = db_handler.get_cards_from_collection(collection_id)?;
+        }
+
+        self.section_name.clear();
+        self.first_label.clear();
+        self.last_label.clear();
+        Ok(())
+    }
+}
@@ -0,0 +1,41 @@
+use eframe::egui::{CentralPanel, Context, ViewportCommand};
+use stickere::error::StickereError;
+use crate::error_text::ErrorText;
+use crate::ui_utils::UiUtils;
+
+/// The application shown instead of `App` when the sticker database cannot be opened at startup.
+/// It reports the problem in a popup and closes once the popup is dismissed,
+/// since a release build on Windows has no console to print the problem to.
+pub struct StartupError {
+    message: String,
+    show_popup: bool,
+}
+
+impl StartupError {
+    /// Creates a new `StartupError` instance reporting the error.
+    ///
+    /// # Arguments
+    ///
+    /// * `err` - A reference to the `StickereError` raised while opening the database.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new instance of `StartupError`.
+    pub fn new(err: &StickereError) -> Self {
+        Self {
+            message: format!("Baza de date a stickerelor nu a putut fi deschisa. {}", err.error_text()),
+            show_popup: true,
+        }
+    }
+}
+
+impl eframe::App for StartupError {
+    fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        CentralPanel::default().show(ctx, |_| {});
+        UiUtils::popup(ctx, &mut self.show_popup, "Eroare la pornire", &self.message);
+
+        if !self.show_popup {
+            ctx.send_viewport_cmd(ViewportCommand::Close);
+        }
+    }
+}
@@ -0,0 +1,266 @@
+use eframe::egui::{CollapsingHeader, Color32, TextEdit, Ui};
+use stickere::card::Card;
+use stickere::checklist;
+use stickere::storage::Storage;
+use stickere::error::StickereError;
+use stickere::history::History;
+use stickere::trade::Trade;
+use stickere::trade_list::{self, TradeList};
+use crate::error_text::ErrorText;
+
+/// A struct that represents the trade panel UI component.
+/// It shows the needs and duplicates of the selected collection as a trade list ready to be shared,
+/// matches it against a trade list or JSON export pasted from another collector,
+/// and records executed trades together with the trade history of the collection.
+pub struct TradePanel {
+    pasted_list: String,
+    theirs: Option<Result<TradeList, String>>,
+    partner: String,
+    given: String,
+    received: String,
+    record_error: Option<String>,
+    trades: Vec<Trade>,
+    trades_collection_id: Option<u32>,
+}
+
+impl Default for TradePanel {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TradePanel {
+    /// Creates a new `TradePanel` instance.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new instance of `TradePanel`.
+    pub fn new() -> Self {
+        Self {
+            pasted_list: String::new(),
+            theirs: None,
+            partner: String::new(),
+            given: String::new(),
+            received: String::new(),
+            record_error: None,
+            trades: vec![],
+            trades_collection_id: None,
+        }
+    }
+
+    /// Renders the trade panel UI.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    /// * `cards` - A mutable reference to a vector of the `Card` objects of the selected collection.
+    /// * `collection_id` - A `u32` representing the identifier of the current selected collection.
+    /// * `history` - A mutable reference to the `History`, cleared when a trade is recorded.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of loading the history or recording a trade, if any.
+    pub fn ui(&mut self, ui: &mut Ui, db_handler: &mut dyn Storage, cards: &mut Vec<Card>,
+              collection_id: u32, history: &mut History) -> Result<(), StickereError> {
+        let mut result = Ok(());
+
+        CollapsingHeader::new("Schimburi")
+            .id_source("TradePanel")
+            .show(ui, |ui| {
+                let trade_list = TradeList::from_cards(cards);
+
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(format!("Lipsa: {}", trade_list.needs_text()));
+                });
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(format!("Dubluri: {}", trade_list.doubles_text()));
+                });
+
+                if ui.button("Copiaza lista").clicked() {
+                    let text = trade_list.to_string();
+                    ui.output_mut(|output| output.copied_text = text);
+                }
+
+                ui.add_space(5.0);
+                self.match_ui(ui, &trade_list);
+
+                ui.add_space(5.0);
+                result = self.record_ui(ui, db_handler, cards, collection_id, history);
+
+                if result.is_ok() {
+                    result = self.history_ui(ui, db_handler, collection_id);
+                }
+            });
+
+        result
+    }
+
+    /// Renders the field for pasting another collector's list and the trade matched against it.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    /// * `trade_list` - A reference to the `TradeList` of the selected collection.
+    fn match_ui(&mut self, ui: &mut Ui, trade_list: &TradeList) {
+        let pasted = ui.add(TextEdit::multiline(&mut self.pasted_list)
+            .hint_text("Lipeste lista sau exportul JSON al unui prieten, ex. Need: 3, 7, 12-19 / Got: 5x2, 18")
+            .desired_rows(2));
+
+        if pasted.changed() {
+            self.theirs = (!self.pasted_list.trim().is_empty()).then(|| Self::parse_pasted(&self.pasted_list));
+        }
+
+        match &self.theirs {
+            Some(Ok(theirs)) => {
+                let trade = trade_list.match_with(theirs);
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(format!("Ii poti da: {}", trade_list::format_labels(&trade.give)));
+                });
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(format!("Poti primi: {}", trade_list::format_labels(&trade.get)));
+                });
+                ui.horizontal_wrapped(|ui| {
+                    if trade.balanced_give.is_empty() {
+                        ui.label("Nu exista un schimb posibil.");
+                        return;
+                    }
+
+                    ui.strong(format!("Schimb propus 1:1: dai {} si primesti {}",
+                                      trade_list::format_labels(&trade.balanced_give),
+                                      trade_list::format_labels(&trade.balanced_get)));
+                    if ui.button("Foloseste").clicked() {
+                        self.given = trade_list::format_labels(&trade.balanced_give);
+                        self.received = trade_list::format_labels(&trade.balanced_get);
+                    }
+                });
+            }
+            Some(Err(message)) => {
+                ui.colored_label(Color32::from_rgb(255, 0, 0), message);
+            }
+            None => {}
+        }
+    }
+
+    /// Renders the form for recording an executed trade and applies the trade when submitted.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    /// * `cards` - A mutable reference to a vector of the `Card` objects of the selected collection.
+    /// * `collection_id` - A `u32` representing the identifier of the current selected collection.
+    /// * `history` - A mutable reference to the `History`, cleared when the trade is recorded
+    ///   since the changes it recorded may no longer apply to the traded cards.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of recording the trade, if any.
+    fn record_ui(&mut self, ui: &mut Ui, db_handler: &mut dyn Storage, cards: &mut Vec<Card>,
+                 collection_id: u32, history: &mut History) -> Result<(), StickereError> {
+        let mut result = Ok(());
+
+        ui.horizontal(|ui| {
+            ui.add(TextEdit::singleline(&mut self.partner)
+                .hint_text("Partener")
+                .desired_width(100.0));
+            ui.add(TextEdit::singleline(&mut self.given)
+                .hint_text("Dai, ex. 5, 12-14")
+                .desired_width(120.0));
+            ui.add(TextEdit::singleline(&mut self.received)
+                .hint_text("Primesti")
+                .desired_width(120.0));
+
+            if !ui.button("Inregistreaza schimbul").clicked() {
+                return;
+            }
+
+            let partner = self.partner.trim();
+            let labels = trade_list::parse_labels(&self.given)
+                .and_then(|given| Ok((given, trade_list::parse_labels(&self.received)?)));
+            let (given, received) = match labels {
+                Ok(_) if partner.is_empty() => {
+                    self.record_error = Some("Completeaza numele partenerului".to_string());
+                    return;
+                }
+                Ok(labels) => labels,
+                Err(err) => {
+                    self.record_error = Some(err.error_text());
+                    return;
+                }
+            };
+            self.record_error = None;
+
+            result = db_handler.record_trade(collection_id, partner, &given, &received)
+                .and_then(|_| db_handler.get_cards_from_collection(collection_id))
+                .map(|updated_cards| {
+                    *cards = updated_cards;
+                    history.clear();
+                    self.partner.clear();
+                    self.given.clear();
+                    self.received.clear();
+                    self.trades_collection_id = None;
+                });
+        });
+
+        if let Some(message) = &self.record_error {
+            ui.colored_label(Color32::from_rgb(255, 0, 0), message);
+        }
+
+        result
+    }
+
+    /// Renders the trade history of the selected collection, loading it when the selection changes.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    /// * `db_handler` - A reference to the `Storage` for database operations.
+    /// * `collection_id` - A `u32` representing the identifier of the current selected collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of loading the history, if any.
+    fn history_ui(&mut self, ui: &mut Ui, db_handler: &dyn Storage, collection_id: u32) -> Result<(), StickereError> {
+        if self.trades_collection_id != Some(collection_id) {
+            self.trades = db_handler.get_trades_from_collection(collection_id)?;
+            self.trades_collection_id = Some(collection_id);
+        }
+
+        CollapsingHeader::new(format!("Istoric schimburi ({})", self.trades.len()))
+            .id_source("TradeHistory")
+            .show(ui, |ui| {
+                for trade in &self.trades {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label(format!("{} - {}: dat {}, primit {}",
+                                         trade.traded_at,
+                                         trade.partner,
+                                         trade_list::format_labels(&trade.given),
+                                         trade_list::format_labels(&trade.received)));
+                    });
+                }
+            });
+
+        Ok(())
+    }
+
+    /// Reads the trade list of another collector, pasted either as a trade list
+    /// or as the JSON export of their collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice holding the pasted text.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TradeList, String>` - The trade list of the other collector, or a description of the problem.
+    fn parse_pasted(text: &str) -> Result<TradeList, String> {
+        if text.trim_start().starts_with('{') {
+            checklist::parse_json(text)
+                .map(|checklist| TradeList::from_checklist(&checklist.cards))
+                .map_err(|errors| errors.iter().map(|error| error.error_text()).collect::<Vec<_>>().join("\n"))
+        } else {
+            TradeList::parse(text).map_err(|err| err.error_text())
+        }
+    }
+}
@@ -0,0 +1,101 @@
+use eframe::egui::{CollapsingHeader, Ui};
+use stickere::collection::{Collection, DeletedCollection};
+use stickere::storage::Storage;
+use stickere::error::StickereError;
+use stickere::history::History;
+use crate::ui_utils::UiUtils;
+
+/// A struct that represents the trash UI component.
+/// It lists the deleted collections, which can be restored or purged permanently.
+pub struct Trash {
+    deleted: Option<Vec<DeletedCollection>>,
+    purge_candidate: Option<DeletedCollection>,
+    confirm_purge: bool,
+}
+
+impl Default for Trash {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Trash {
+    /// Creates a new `Trash` instance.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new instance of `Trash`.
+    pub fn new() -> Self {
+        Self {
+            deleted: None,
+            purge_candidate: None,
+            confirm_purge: false,
+        }
+    }
+
+    /// Marks the list of deleted collections as outdated, so it is loaded again on the next frame.
+    pub fn refresh(&mut self) {
+        self.deleted = None;
+    }
+
+    /// Renders the trash UI, restoring or purging collections on request.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    /// * `collections` - A mutable reference to a vector of `Collection` objects, reloaded when a collection is restored.
+    /// * `history` - A mutable reference to the `History`, cleared when a collection is purged
+    ///   since the changes it recorded may refer to the purged collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of loading, restoring or purging collections, if any.
+    pub fn ui(&mut self, ui: &mut Ui, db_handler: &mut dyn Storage, collections: &mut Vec<Collection>,
+              history: &mut History) -> Result<(), StickereError> {
+        if self.confirm_purge {
+            let prompt = format!("Stergi definitiv catalogul \"{}\"? Actiunea nu poate fi anulata.",
+                                 self.purge_candidate.as_ref().map_or("", |collection| &collection.name));
+            if UiUtils::confirm(ui.ctx(), &mut self.confirm_purge, "Sterge definitiv", &prompt) {
+                if let Some(collection) = self.purge_candidate.take() {
+                    db_handler.purge_collection(collection.id)?;
+                    history.clear();
+                    self.refresh();
+                }
+            }
+        }
+
+        let deleted = match self.deleted.take() {
+            Some(deleted) => deleted,
+            None => db_handler.get_deleted_collections()?,
+        };
+        let mut restored = None;
+
+        CollapsingHeader::new(format!("Cos de gunoi ({})", deleted.len()))
+            .id_source("Trash")
+            .show(ui, |ui| {
+                for collection in &deleted {
+                    ui.label(&collection.name)
+                        .on_hover_text(format!("Sters la {}", collection.deleted_at));
+                    ui.horizontal(|ui| {
+                        if ui.button("Restaureaza").clicked() {
+                            restored = Some(collection.id);
+                        }
+                        if ui.button("Sterge definitiv").clicked() {
+                            self.purge_candidate = Some(collection.clone());
+                            self.confirm_purge = true;
+                        }
+                    });
+                }
+            });
+        self.deleted = Some(deleted);
+
+        if let Some(collection_id) = restored {
+            db_handler.restore_collection(collection_id)?;
+            *collections = db_handler.get_collections()?;
+            self.refresh();
+        }
+
+        Ok(())
+    }
+}
@@ -1,23 +1,55 @@
-use eframe::egui::{Vec2, Ui, ScrollArea, Grid, Layout, Align, Context, CentralPanel};
+use eframe::egui::{menu, Vec2, Ui, ScrollArea, Grid, Layout, Align, Context, CentralPanel, CollapsingHeader, Key,
+                   KeyboardShortcut, Modifiers, TopBottomPanel};
 
-use crate::card::Card;
-use crate::collection::Collection;
+use stickere::card::Card;
+use crate::card_ui::{CardAction, CardUi};
+use stickere::collection::Collection;
+use crate::collection_ui::CollectionUi;
 use crate::collection_adder::CollectionAdder;
 use crate::collection_settings::CollectionSettings;
-use crate::db::DbHandler;
+use stickere::collection_stats::CollectionStats;
+use crate::collection_stats_ui::CollectionStatsUi;
+use stickere::storage::Storage;
 use crate::card_adder::CardAdder;
+use crate::checklist_importer::ChecklistImporter;
+use stickere::error::StickereError;
+use stickere::history::{Command, History};
+use stickere::label;
+use stickere::section::Section;
+use crate::section_adder::SectionAdder;
+use crate::trade_panel::TradePanel;
+use crate::trash::Trash;
+use crate::pack_opener::PackOpener;
+use crate::profile_menu::ProfileMenu;
+use crate::backup_dialog::BackupDialog;
+use crate::error_text::ErrorText;
+use crate::ui_utils::UiUtils;
 
 /// A struct that represents the main application.
 /// It holds the UI state of the application.
 pub struct App {
-    db_handler: DbHandler,
+    db_handler: Box<dyn Storage>,
     cards: Vec<Card>,
+    sections: Vec<Section>,
     collections: Vec<Collection>,
     collection_adder: CollectionAdder,
+    checklist_importer: ChecklistImporter,
     collection_settings: CollectionSettings,
     selected_collection_id: Option<u32>,
     selected_collection_name: Option<String>,
     card_adder: CardAdder,
+    section_adder: SectionAdder,
+    trade_panel: TradePanel,
+    pack_opener: PackOpener,
+    history: History,
+    trash: Trash,
+    profile_menu: ProfileMenu,
+    backup_dialog: BackupDialog,
+    card_action: Option<CardAction>,
+    card_to_delete: Option<u32>,
+    confirm_card_delete: bool,
+    show_error_popup: bool,
+    error_message: String,
 }
 
 impl App {
@@ -26,43 +58,257 @@ impl App {
     /// # Arguments
     ///
     /// * `_cc` - A reference to the `eframe::CreationContext`.
-    /// * `db_handler` - A `DbHandler` instance for database operations.
+    /// * `db_handler` - The `Storage` holding the collections, such as the SQLite `DbHandler`.
+    /// * `profile_menu` - The `ProfileMenu` for switching to the database of another profile.
     ///
     /// # Returns
     ///
     /// * `Self` - A new instance of `App`.
-    pub fn new(_cc: &eframe::CreationContext<'_>, db_handler: DbHandler) -> Self {
+    pub fn new(_cc: &eframe::CreationContext<'_>, db_handler: Box<dyn Storage>, profile_menu: ProfileMenu) -> Self {
         // Customize egui here with cc. egui_ctx. set_fonts and cc. egui_ctx. set_visuals.
         // Restore app state using cc. storage (requires the "persistence" feature).
         // Use the cc. gl (a glow::Context) to create graphics shaders and buffers that you can use
         // for e.g. egui::PaintCallback.
         let collections = db_handler.get_collections();
-        Self {
+        let mut app = Self {
             db_handler,
             cards: vec![],
-            collections,
+            sections: vec![],
+            collections: vec![],
             collection_adder: CollectionAdder::new(),
+            checklist_importer: ChecklistImporter::new(),
             collection_settings: CollectionSettings::new(),
             selected_collection_id: None,
             selected_collection_name: None,
             card_adder: CardAdder::new(),
+            section_adder: SectionAdder::new(),
+            trade_panel: TradePanel::new(),
+            pack_opener: PackOpener::new(),
+            history: History::new(),
+            trash: Trash::new(),
+            profile_menu,
+            backup_dialog: BackupDialog::new(),
+            card_action: None,
+            card_to_delete: None,
+            confirm_card_delete: false,
+            show_error_popup: false,
+            error_message: String::new(),
+        };
+
+        match collections {
+            Ok(collections) => app.collections = collections,
+            Err(err) => app.report_error(Err(err)),
+        }
+        app
+    }
+
+    /// Stores the error of a failed operation so it is shown in a popup on the next frame.
+    ///
+    /// # Arguments
+    ///
+    /// * `result` - The `Result` of the operation, ignored if it succeeded.
+    fn report_error(&mut self, result: Result<(), StickereError>) {
+        if let Err(err) = result {
+            self.error_message = err.error_text();
+            self.show_error_popup = true;
+        }
+    }
+
+    /// Starts over with the database of another profile, dropping everything loaded from the previous one,
+    /// including the changes that could be undone.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_handler` - The `Storage` of the other profile.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of loading the collections of the profile, if any.
+    fn switch_storage(&mut self, db_handler: Box<dyn Storage>) -> Result<(), StickereError> {
+        self.db_handler = db_handler;
+        self.start_over()
+    }
+
+    /// Drops everything loaded from the database, including the changes that could be undone, and loads
+    /// the collections again, after the database was switched or restored from a backup.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of loading the collections, if any.
+    fn start_over(&mut self) -> Result<(), StickereError> {
+        self.history.clear();
+        self.trash = Trash::new();
+        self.trade_panel = TradePanel::new();
+        self.pack_opener = PackOpener::new();
+        self.collection_settings = CollectionSettings::new();
+        self.backup_dialog = BackupDialog::new();
+        self.selected_collection_id = None;
+        self.selected_collection_name = None;
+        self.cards.clear();
+        self.sections.clear();
+        self.collections = self.db_handler.get_collections()?;
+        Ok(())
+    }
+
+    /// Moves the selected collection to the trash, recording the deletion so it can be undone.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of deleting the collection, if any.
+    fn delete_selected_collection(&mut self) -> Result<(), StickereError> {
+        let Some(collection_id) = self.selected_collection_id else {
+            return Ok(());
+        };
+
+        self.collection_settings.delete_collection(self.db_handler.as_mut(), &mut self.history,
+                                                   &mut self.collections, collection_id)?;
+        self.trash.refresh();
+
+        self.selected_collection_id = None;
+        self.selected_collection_name = None;
+        self.cards.clear();
+        self.sections.clear();
+        Ok(())
+    }
+
+    /// Carries out the change requested from the context menu of a card, if any.
+    /// Deleting a card that is collected or has duplicates is only done once confirmed.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - A reference to the `Context` object for rendering the confirmation.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of updating the card, if any.
+    fn handle_card_action(&mut self, ctx: &Context) -> Result<(), StickereError> {
+        match self.card_action.take() {
+            Some(CardAction::Relabel { card_id, label }) => {
+                self.db_handler.update_card_label(card_id, &label)?;
+                if let Some(card) = self.cards.iter_mut().find(|card| card.id == card_id) {
+                    let before = std::mem::replace(&mut card.label, label.clone());
+                    self.history.push(Command::RelabelCard { card_id, before, after: label });
+                }
+                self.cards.sort_by(|a, b| label::natural_cmp(&a.label, &b.label));
+            }
+            Some(CardAction::Delete { card_id }) => {
+                let in_use = self.cards.iter()
+                    .any(|card| card.id == card_id && (card.checked || card.duplicates > 0));
+                if in_use {
+                    self.card_to_delete = Some(card_id);
+                    self.confirm_card_delete = true;
+                } else {
+                    self.delete_card(card_id)?;
+                }
+            }
+            None => {}
+        }
+
+        if self.confirm_card_delete {
+            let label = self.cards.iter()
+                .find(|card| Some(card.id) == self.card_to_delete)
+                .map_or("", |card| &card.label);
+            let prompt = format!("Stickerul {label} este colectat sau are dubluri. Il stergi?");
+            if UiUtils::confirm(ctx, &mut self.confirm_card_delete, "Sterge stickerul", &prompt) {
+                if let Some(card_id) = self.card_to_delete.take() {
+                    self.delete_card(card_id)?;
+                }
+            }
         }
+        Ok(())
     }
 
+    /// Deletes a card of the selected collection, recording the deletion so it can be undone.
+    ///
+    /// # Arguments
+    ///
+    /// * `card_id` - A `u32` representing the card identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of deleting the card, if any.
+    fn delete_card(&mut self, card_id: u32) -> Result<(), StickereError> {
+        let snapshot = self.db_handler.snapshot_card(card_id)?;
+        self.db_handler.delete_card(card_id)?;
+        self.history.push(Command::DeleteCard { card_id, snapshot: Some(snapshot) });
+        self.cards.retain(|card| card.id != card_id);
+        Ok(())
+    }
+
+    /// Undoes or redoes the most recent change when Ctrl+Z or Ctrl+Shift+Z is pressed,
+    /// unless a text field is being edited, then reloads what the change affected.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - A reference to the `Context` object holding the keyboard input.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of undoing or redoing the change, if any.
+    fn handle_history_shortcuts(&mut self, ctx: &Context) -> Result<(), StickereError> {
+        if ctx.wants_keyboard_input() {
+            return Ok(());
+        }
+
+        // The redo shortcut is checked first, since the undo shortcut would also match it.
+        let redo = ctx.input_mut(|input| {
+            input.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z))
+        });
+        let undo = !redo && ctx.input_mut(|input| {
+            input.consume_shortcut(&KeyboardShortcut::new(Modifiers::COMMAND, Key::Z))
+        });
+
+        let changed = if redo {
+            self.history.redo(self.db_handler.as_mut())?
+        } else if undo {
+            self.history.undo(self.db_handler.as_mut())?
+        } else {
+            false
+        };
+
+        if changed {
+            self.reload()?;
+        }
+        Ok(())
+    }
+
+    /// Reloads the collections, the trash, and the cards and sections of the selected collection,
+    /// clearing the selection if the selected collection no longer exists.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of loading from the database, if any.
+    fn reload(&mut self) -> Result<(), StickereError> {
+        self.collections = self.db_handler.get_collections()?;
+        self.trash.refresh();
+
+        let selected = self.selected_collection_id
+            .filter(|id| self.collections.iter().any(|collection| collection.id == *id));
+        match selected {
+            Some(collection_id) => {
+                self.cards = self.db_handler.get_cards_from_collection(collection_id)?;
+                self.sections = self.db_handler.get_sections_from_collection(collection_id)?;
+            }
+            None => {
+                self.selected_collection_id = None;
+                self.selected_collection_name = None;
+                self.cards.clear();
+                self.sections.clear();
+            }
+        }
+        Ok(())
+    }
 
     /// Renders the card grid UI.
-    /// This grid contains all the cards of a selected card collection
+    /// This grid contains all the cards of a selected card collection.
+    /// If the collection has sections, every section is rendered as a collapsible header
+    /// showing its completion, followed by the cards without a section.
     ///
     /// # Arguments
     ///
     /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
     /// * `available_height` - A `f32` representing the available height for the card grid.
     fn card_grid(&mut self, ui: &mut Ui, available_height: f32) {
-        let available_width = ui.available_width();
-        let item_width = 40.0;
-        let spacing = 5.0;
-        let num_columns = ((available_width + spacing) / (item_width + spacing)).floor() as usize;
-
         let name = match &self.selected_collection_name {
             Some(x) => x.to_owned(),
             None => "Carduri".to_string()
@@ -71,37 +317,98 @@ impl App {
         ui.label(name + ":");
         ui.add_space(5.0);
 
-        let filtered_cards_iter = self.cards.iter_mut()
-            .filter(|x| {
-                (self.collection_settings.show_collected && x.checked) ||
-                    (self.collection_settings.show_not_collected && !x.checked)
-            });
-
+        let mut result = Ok(());
+        let show_collected = self.collection_settings.show_collected;
+        let show_not_collected = self.collection_settings.show_not_collected;
+        let is_shown = |card: &Card| {
+            (show_collected && card.checked) || (show_not_collected && !card.checked)
+        };
 
         ScrollArea::vertical()
             .auto_shrink([false; 2])
             .max_height(available_height)
             .show(ui, |ui| {
-                Grid::new("Checkbox")
-                    .min_col_width(item_width)
-                    .spacing([spacing, spacing])
-                    .striped(true)
-                    .show(ui, |ui| {
-                        for (i, check_box) in filtered_cards_iter.enumerate() {
-                            check_box.ui(ui, &self.db_handler);
-
-                            if num_columns != 0 && i % num_columns == num_columns - 1 {
-                                ui.end_row()
+                if self.sections.is_empty() {
+                    result = Self::cards_grid(ui, None, self.cards.iter_mut().filter(|card| is_shown(card)),
+                                              self.db_handler.as_mut(), &mut self.history, &mut self.card_action);
+                    return;
+                }
+
+                for section in self.sections.iter().map(Some).chain([None]) {
+                    let section_id = section.map(|section| section.id);
+                    let (collected, total) = Section::completion(section_id, &self.cards);
+                    if section.is_none() && total == 0 {
+                        continue;
+                    }
+
+                    let name = section.map_or("Fara sectiune", |section| &section.name);
+                    CollapsingHeader::new(format!("{name} ({collected}/{total})"))
+                        .id_source(("Section", section_id))
+                        .default_open(true)
+                        .show(ui, |ui| {
+                            let cards = self.cards.iter_mut()
+                                .filter(|card| card.section_id == section_id && is_shown(card));
+                            let section_result = Self::cards_grid(ui, section_id, cards, self.db_handler.as_mut(),
+                                                                  &mut self.history, &mut self.card_action);
+                            if result.is_ok() {
+                                result = section_result;
                             }
-                        }
-                    });
+                        });
+                }
             });
+
+        self.report_error(result);
+    }
+
+    /// Renders a grid of cards, wrapping them into as many columns as fit the available width.
+    ///
+    /// # Arguments
+    ///
+    /// * `ui` - A mutable reference to the parent `Ui` object for rendering.
+    /// * `section_id` - The identifier of the section the cards belong to, used to keep the grid id unique.
+    /// * `cards` - An iterator over the `Card` objects to render.
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    /// * `history` - A mutable reference to the `History` recording the changes of the cards.
+    /// * `action` - A mutable reference to an `Option<CardAction>`, set when a card requests a change.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - The error of a database update triggered by a card, if any.
+    fn cards_grid<'a>(ui: &mut Ui, section_id: Option<u32>, cards: impl Iterator<Item=&'a mut Card>,
+                      db_handler: &mut dyn Storage, history: &mut History,
+                      action: &mut Option<CardAction>) -> Result<(), StickereError> {
+        let available_width = ui.available_width();
+        let item_width = 40.0;
+        let spacing = 5.0;
+        let num_columns = ((available_width + spacing) / (item_width + spacing)).floor() as usize;
+        let mut result = Ok(());
+
+        Grid::new(("Checkbox", section_id))
+            .min_col_width(item_width)
+            .spacing([spacing, spacing])
+            .striped(true)
+            .show(ui, |ui| {
+                for (i, check_box) in cards.enumerate() {
+                    let card_result = check_box.ui(ui, db_handler, history, action);
+                    if result.is_ok() {
+                        result = card_result;
+                    }
+
+                    if num_columns != 0 && i % num_columns == num_columns - 1 {
+                        ui.end_row()
+                    }
+                }
+            });
+
+        result
     }
 
     /// Renders the right section of the UI.
     /// This section contains:
     /// - collection settings.
+    /// - the trade panel with the needs and duplicates of the selected collection.
     /// - the grid of cards of the selected collection.
+    /// - the section adder for grouping the cards of the collection into sections.
     /// - the card adder for adding new card to the collection.
     ///
     /// # Arguments
@@ -110,22 +417,55 @@ impl App {
     fn right_section(&mut self, ui: &mut Ui) {
         ui.vertical(|ui| {
             if let Some(selected_id) = self.selected_collection_id {
-                self.collection_settings.ui(ui,
-                                            &self.db_handler,
-                                            &mut self.collections,
-                                            &mut self.cards,
-                                            &mut self.selected_collection_id,
-                                            &mut self.selected_collection_name);
+                let result = self.collection_settings.ui(ui,
+                                                         self.db_handler.as_mut(),
+                                                         &mut self.collections,
+                                                         &mut self.cards,
+                                                         self.selected_collection_id,
+                                                         &mut self.selected_collection_name);
+                self.report_error(result);
+
+                if self.collection_settings.resized {
+                    self.collection_settings.resized = false;
+                    self.history.clear();
+                }
+
+                if self.collection_settings.delete_requested {
+                    self.collection_settings.delete_requested = false;
+                    let result = self.delete_selected_collection();
+                    self.report_error(result);
+                    return;
+                }
+
+                CollectionStats::new(&self.cards, &self.sections).ui(ui);
+
+                let result = self.trade_panel.ui(ui, self.db_handler.as_mut(), &mut self.cards, selected_id,
+                                                 &mut self.history);
+                self.report_error(result);
+
+                let result = self.pack_opener.ui(ui, self.db_handler.as_mut(), &mut self.cards, selected_id,
+                                                 &mut self.history);
+                self.report_error(result);
 
                 let available_height = ui.available_height();
-                let card_grid_height = available_height - 75.0;
+                let card_grid_height = available_height - 110.0;
 
                 ui.separator();
                 self.card_grid(ui, card_grid_height);
+                let result = self.handle_card_action(ui.ctx());
+                self.report_error(result);
 
 
                 ui.separator();
-                self.card_adder.ui(ui, &mut self.db_handler, &mut self.cards, selected_id);
+                let result = self.section_adder.ui(ui,
+                                                   self.db_handler.as_mut(),
+                                                   &mut self.sections,
+                                                   &mut self.cards,
+                                                   selected_id);
+                self.report_error(result);
+
+                let result = self.card_adder.ui(ui, self.db_handler.as_mut(), &mut self.cards, selected_id, &mut self.history);
+                self.report_error(result);
             }
         });
     }
@@ -133,6 +473,7 @@ impl App {
     /// Renders the left section of the UI.
     /// It contains:
     /// - Collection adder component that adds new collections with name and size.
+    /// - Checklist importer component that adds new collections from a checklist file.
     /// - List of all the collections in the database.
     ///
     /// # Arguments
@@ -146,7 +487,11 @@ impl App {
                 ui.set_max_width(150.0);
 
                 ui.vertical(|ui| {
-                    self.collection_adder.ui(ui, &mut self.collections, &mut self.db_handler);
+                    let result = self.collection_adder.ui(ui, &mut self.collections, self.db_handler.as_mut());
+                    self.report_error(result);
+
+                    let result = self.checklist_importer.ui(ui, &mut self.collections, self.db_handler.as_mut());
+                    self.report_error(result);
 
                     ui.separator();
 
@@ -158,13 +503,30 @@ impl App {
 
                             ui.add_space(5.0);
 
+                            let selected = self.collections.iter_mut()
+                                .find(|collection| Some(collection.id) == self.selected_collection_id);
+                            if let Some(collection) = selected {
+                                collection.update_progress(&self.cards);
+                            }
+
+                            let mut result = Ok(());
                             for collection in &self.collections {
-                                collection.ui(ui,
-                                              &mut self.cards,
-                                              &self.db_handler,
-                                              &mut self.selected_collection_id,
-                                              &mut self.selected_collection_name);
+                                let collection_result = collection.ui(ui,
+                                                                      &mut self.cards,
+                                                                      &mut self.sections,
+                                                                      self.db_handler.as_ref(),
+                                                                      &mut self.selected_collection_id,
+                                                                      &mut self.selected_collection_name);
+                                if result.is_ok() {
+                                    result = collection_result;
+                                }
                             }
+                            self.report_error(result);
+
+                            ui.separator();
+                            let result = self.trash.ui(ui, self.db_handler.as_mut(), &mut self.collections,
+                                                       &mut self.history);
+                            self.report_error(result);
                         });
                 });
             },
@@ -201,6 +563,31 @@ impl eframe::App for App {
     /// * `ctx` - A reference to the `Context` object for rendering.
     /// * `_frame` - A mutable reference to the `eframe::Frame` object.
     fn update(&mut self, ctx: &Context, _frame: &mut eframe::Frame) {
+        if self.show_error_popup {
+            UiUtils::popup(ctx, &mut self.show_error_popup, "Eroare", &self.error_message);
+        }
+
+        let result = self.handle_history_shortcuts(ctx);
+        self.report_error(result);
+
+        TopBottomPanel::top("MenuBar").show(ctx, |ui| {
+            menu::bar(ui, |ui| {
+                let result = match self.profile_menu.ui(ui) {
+                    Ok(Some(db_handler)) => self.switch_storage(Box::new(db_handler)),
+                    Ok(None) => Ok(()),
+                    Err(err) => Err(err),
+                };
+                self.report_error(result);
+
+                let result = match self.backup_dialog.ui(ui, self.db_handler.as_mut()) {
+                    Ok(true) => self.start_over(),
+                    Ok(false) => Ok(()),
+                    Err(err) => Err(err),
+                };
+                self.report_error(result);
+            });
+        });
+
         CentralPanel::default().show(ctx, |ui| {
             self.main_layout(ui);
         });
@@ -1,4 +1,4 @@
-use eframe::egui::{Color32, Context, RichText, Vec2, Window};
+use eframe::egui::{Color32, Context, RichText, ScrollArea, Vec2, Window};
 
 /// A utility struct for UI-related functions.
 pub struct UiUtils;
@@ -38,4 +38,85 @@ impl UiUtils {
                 })
             });
     }
-}
\ No newline at end of file
+
+    /// Displays a popup window with a title and a scrollable list of messages,
+    /// for reporting several problems at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - A reference to the `Context` object for rendering.
+    /// * `show_popup` - A mutable reference to a boolean that determines whether the popup is shown.
+    /// * `title` - A string slice that holds the title of the popup.
+    /// * `messages` - A slice of the messages to list.
+    pub fn list_popup(ctx: &Context, show_popup: &mut bool, title: &str, messages: &[String]) {
+        let pos = Vec2::from([400.0, 300.0]);
+        let screen_rect = ctx.screen_rect();
+        let center = screen_rect.center() - pos * 0.5;
+
+        Window::new(RichText::from(title).color(Color32::from_rgb(255, 0, 0)))
+            .collapsible(false)
+            .resizable(false)
+            .fixed_size(pos)
+            .fixed_pos(center)
+            .show(ctx, |ui| {
+                ScrollArea::vertical()
+                    .max_height(pos.y - 50.0)
+                    .show(ui, |ui| {
+                        for message in messages {
+                            ui.colored_label(Color32::from_rgb(255, 0, 0), message);
+                        }
+                    });
+
+                ui.add_space(10.0);
+                ui.vertical_centered(|ui| {
+                    if ui.button("OK").clicked() {
+                        *show_popup = false;
+                    }
+                });
+            });
+    }
+
+    /// Displays a popup window asking the user to confirm an action.
+    ///
+    /// # Arguments
+    ///
+    /// * `ctx` - A reference to the `Context` object for rendering.
+    /// * `show_popup` - A mutable reference to a boolean that determines whether the popup is shown.
+    /// * `title` - A string slice that holds the title of the popup.
+    /// * `prompt` - A string slice that holds the question of the popup.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `true` in the frame the action is confirmed. The popup is closed by either answer.
+    pub fn confirm(ctx: &Context, show_popup: &mut bool, title: &str, prompt: &str) -> bool {
+        let pos = Vec2::from([300.0, 200.0]);
+        let screen_rect = ctx.screen_rect();
+        let center = screen_rect.center() - pos * 0.5;
+        let mut confirmed = false;
+
+        Window::new(RichText::from(title).color(Color32::from_rgb(255, 0, 0)))
+            .collapsible(false)
+            .resizable(false)
+            .fixed_size(pos)
+            .fixed_pos(center)
+            .show(ctx, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.add_space(10.0);
+                    ui.label(prompt);
+                    ui.add_space(10.0);
+
+                    ui.horizontal(|ui| {
+                        if ui.button("Da").clicked() {
+                            confirmed = true;
+                            *show_popup = false;
+                        }
+                        if ui.button("Nu").clicked() {
+                            *show_popup = false;
+                        }
+                    });
+                })
+            });
+
+        confirmed
+    }
+}
@@ -0,0 +1,215 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+
+use crate::error::StickereError;
+
+/// The number of backups kept for every database, older backups being removed.
+pub const MAX_BACKUPS: usize = 10;
+
+/// The file of a backup directory recording the size and modification time the database file had
+/// when its most recent backup was taken.
+const STATE_FILE: &str = "backed-up.txt";
+
+/// A copy of the database taken at some point, which it can be restored from.
+///
+/// # Fields
+/// * `path` - The path of the backup file.
+/// * `taken_at` - A `String` representing the local date and time of the backup, as `YYYY-MM-DD HH:MM:SS`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backup {
+    pub path: PathBuf,
+    pub taken_at: String,
+}
+
+/// Returns the directory holding the backups of a database file: a `backups` directory next to the file,
+/// with a directory for every database, e.g. `backups/default` for `default.db`.
+///
+/// # Arguments
+///
+/// * `db_path` - The path of the database file.
+///
+/// # Returns
+///
+/// * `PathBuf` - The directory of the backups.
+pub fn backup_dir(db_path: &Path) -> PathBuf {
+    let name = db_path.file_stem().unwrap_or_default();
+    db_path.with_file_name("backups").join(name)
+}
+
+/// Returns the path of a new backup, named after the time it is taken, e.g. `2024-06-14_21-05-09.123.db`.
+/// A counter is appended to the name of a backup taken in the same millisecond as another one, e.g. `..._001.db`,
+/// padded so the names still sort in the order the backups were taken.
+///
+/// # Arguments
+///
+/// * `dir` - The directory of the backups.
+/// * `timestamp` - A string slice representing the local date and time, as `YYYY-MM-DD HH:MM:SS.SSS`.
+///
+/// # Returns
+///
+/// * `PathBuf` - The path of the backup file.
+pub fn backup_path(dir: &Path, timestamp: &str) -> PathBuf {
+    let name = timestamp.replace(' ', "_").replace(':', "-");
+    let mut path = dir.join(format!("{name}.db"));
+    let mut counter = 1;
+    while path.exists() {
+        path = dir.join(format!("{name}_{counter:03}.db"));
+        counter += 1;
+    }
+    path
+}
+
+/// Lists the backups of a directory, most recent first.
+///
+/// # Arguments
+///
+/// * `dir` - The directory of the backups.
+///
+/// # Returns
+///
+/// * `Result<Vec<Backup>, StickereError>` - The backups, none if the directory does not exist.
+pub fn list(dir: &Path) -> Result<Vec<Backup>, StickereError> {
+    let mut backups = vec![];
+
+    if dir.exists() {
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if let Some(taken_at) = taken_at(&path) {
+                backups.push(Backup { path, taken_at });
+            }
+        }
+    }
+
+    backups.sort_by(|a, b| b.path.cmp(&a.path));
+    Ok(backups)
+}
+
+/// Records the size and modification time of a database file after a backup of it was taken,
+/// for `is_up_to_date` to compare against.
+///
+/// # Arguments
+///
+/// * `db_path` - The path of the database file.
+/// * `dir` - The directory of the backups.
+///
+/// # Returns
+///
+/// * `Result<(), StickereError>` - A result indicating success or failure.
+pub fn record_state(db_path: &Path, dir: &Path) -> Result<(), StickereError> {
+    fs::write(dir.join(STATE_FILE), file_state(db_path)?)?;
+    Ok(())
+}
+
+/// Checks whether a database file is unchanged since its most recent backup was taken,
+/// so opening it again, e.g. for a read-only command, does not push out older backups.
+/// The file counts as changed if its size or modification time differs from the ones recorded by `record_state`.
+///
+/// # Arguments
+///
+/// * `db_path` - The path of the database file.
+/// * `dir` - The directory of the backups.
+///
+/// # Returns
+///
+/// * `Result<bool, StickereError>` - Whether the database file is unchanged since its most recent backup.
+pub fn is_up_to_date(db_path: &Path, dir: &Path) -> Result<bool, StickereError> {
+    if list(dir)?.is_empty() {
+        return Ok(false);
+    }
+
+    let recorded = fs::read_to_string(dir.join(STATE_FILE)).unwrap_or_default();
+    Ok(recorded == file_state(db_path)?)
+}
+
+/// Removes the oldest backups of a directory, keeping the most recent ones.
+///
+/// # Arguments
+///
+/// * `dir` - The directory of the backups.
+/// * `keep` - The number of backups to keep.
+///
+/// # Returns
+///
+/// * `Result<(), StickereError>` - A result indicating success or failure.
+pub fn prune(dir: &Path, keep: usize) -> Result<(), StickereError> {
+    for backup in list(dir)?.iter().skip(keep) {
+        fs::remove_file(&backup.path)?;
+    }
+    Ok(())
+}
+
+/// Describes the size and modification time of a database file, which change whenever SQLite writes to it.
+///
+/// # Arguments
+///
+/// * `db_path` - The path of the database file.
+///
+/// # Returns
+///
+/// * `Result<String, StickereError>` - The size in bytes and the modification time in nanoseconds.
+fn file_state(db_path: &Path) -> Result<String, StickereError> {
+    let metadata = fs::metadata(db_path)?;
+    let modified = metadata.modified()?.duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_nanos());
+    Ok(format!("{} {modified}", metadata.len()))
+}
+
+/// Reads the time a backup was taken from the name of its file.
+///
+/// # Arguments
+///
+/// * `path` - The path of the backup file.
+///
+/// # Returns
+///
+/// * `Option<String>` - The local date and time, as `YYYY-MM-DD HH:MM:SS`, if the file is named like a backup.
+fn taken_at(path: &Path) -> Option<String> {
+    if path.extension()? != "db" {
+        return None;
+    }
+
+    let (date, time) = path.file_stem()?.to_str()?.split_once('_')?;
+    let (time, _millis) = time.split_once('.').unwrap_or((time, ""));
+    let valid = date.len() == 10 && time.len() == 8
+        && date.chars().chain(time.chars()).all(|c| c.is_ascii_digit() || c == '-');
+    valid.then(|| format!("{date} {}", time.replace('-', ":")))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn backups_are_named_after_their_time_and_pruned_oldest_first() {
+        let dir = std::env::temp_dir().join(format!("stickere-backups-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let timestamps = ["2024-06-14 21:05:09.123", "2024-06-15 08:00:00.000"]
+            .into_iter()
+            .chain(std::iter::repeat_n("2024-06-14 21:05:09.123", 10));
+        for timestamp in timestamps {
+            fs::write(backup_path(&dir, timestamp), "").unwrap();
+        }
+        fs::write(dir.join("notes.db"), "").unwrap();
+
+        let backups = list(&dir).unwrap();
+        assert_eq!(backups[0].taken_at, "2024-06-15 08:00:00");
+        assert_eq!(backups[1].path, dir.join("2024-06-14_21-05-09.123_010.db"));
+        assert_eq!(backups[2].path, dir.join("2024-06-14_21-05-09.123_009.db"));
+        assert_eq!(backups.last().unwrap().path, dir.join("2024-06-14_21-05-09.123.db"));
+        assert!(backups[1..].iter().all(|backup| backup.taken_at == "2024-06-14 21:05:09"));
+
+        prune(&dir, 1).unwrap();
+        assert_eq!(list(&dir).unwrap(), [Backup {
+            path: dir.join("2024-06-15_08-00-00.000.db"),
+            taken_at: "2024-06-15 08:00:00".to_string(),
+        }]);
+        assert!(dir.join("notes.db").exists());
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn backups_are_kept_next_to_their_database() {
+        assert_eq!(backup_dir(Path::new("/data/stickere/default.db")), Path::new("/data/stickere/backups/default"));
+    }
+}
@@ -0,0 +1,41 @@
+use crate::card_details::CardDetails;
+
+/// Represents a card with a label, id, checked status, number of duplicates, the section it belongs to
+/// and its descriptive details.
+#[derive(Debug, Clone)]
+pub struct Card {
+    pub label: String,
+    pub id: u32,
+    pub checked: bool,
+    pub duplicates: u32,
+    pub section_id: Option<u32>,
+    pub details: CardDetails,
+}
+
+impl Card {
+    /// Creates a new `Card` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `label` - A `String` representing the label of the card, e.g. `"7"` or `"FWC 5"`.
+    /// * `id` - A `u32` representing the unique identifier of the card.
+    /// * `checked` - A `bool` indicating whether the card is checked (collected).
+    /// * `duplicates` - A `u32` representing the number of duplicates of the card.
+    /// * `section_id` - An `Option<u32>` representing the section of the card, if it has one.
+    /// * `details` - The `CardDetails` of the card.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new instance of `Card`.
+    pub fn new(label: String, id: u32, checked: bool, duplicates: u32, section_id: Option<u32>,
+               details: CardDetails) -> Self {
+        Self {
+            label,
+            id,
+            checked,
+            duplicates,
+            section_id,
+            details,
+        }
+    }
+}
@@ -0,0 +1,81 @@
+use rusqlite::types::{FromSql, FromSqlError, FromSqlResult, ToSql, ToSqlOutput, ValueRef};
+
+/// The rarity of a card, as printed by the album publisher.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Rarity {
+    #[default]
+    Common,
+    Foil,
+    Legend,
+}
+
+impl Rarity {
+    /// All rarities, from the most to the least common.
+    pub const ALL: [Rarity; 3] = [Rarity::Common, Rarity::Foil, Rarity::Legend];
+
+    /// Returns the identifier of the rarity, as stored in the database.
+    ///
+    /// # Returns
+    ///
+    /// * `&'static str` - The identifier of the rarity.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Rarity::Common => "common",
+            Rarity::Foil => "foil",
+            Rarity::Legend => "legend",
+        }
+    }
+
+    /// Parses a rarity from its identifier, ignoring case and surrounding whitespace.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - A string slice holding the identifier of the rarity.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<Rarity>` - The parsed rarity, or `None` if the identifier is unknown.
+    pub fn parse(value: &str) -> Option<Rarity> {
+        let value = value.trim();
+        Rarity::ALL.into_iter().find(|rarity| rarity.as_str().eq_ignore_ascii_case(value))
+    }
+}
+
+impl ToSql for Rarity {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        Ok(ToSqlOutput::from(self.as_str()))
+    }
+}
+
+impl FromSql for Rarity {
+    fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
+        Rarity::parse(value.as_str()?).ok_or(FromSqlError::InvalidType)
+    }
+}
+
+/// The optional descriptive details of a card.
+/// Empty strings mean the detail was not filled in and are stored as `NULL` in the database.
+///
+/// # Fields
+/// * `name` - The name of the player or of the sticker.
+/// * `category` - The team or category of the card.
+/// * `rarity` - The `Rarity` of the card.
+/// * `notes` - Free text notes about the card.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct CardDetails {
+    pub name: String,
+    pub category: String,
+    pub rarity: Rarity,
+    pub notes: String,
+}
+
+impl CardDetails {
+    /// Checks whether any detail was filled in.
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - `true` if all details are empty and the rarity is common.
+    pub fn is_empty(&self) -> bool {
+        self.name.is_empty() && self.category.is_empty() && self.rarity == Rarity::Common && self.notes.is_empty()
+    }
+}
@@ -0,0 +1,425 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use crate::card_details::{CardDetails, Rarity};
+use crate::error::StickereError;
+
+/// A card of a checklist, validated and ready to be added to a collection.
+///
+/// # Fields
+/// * `label` - The label of the card.
+/// * `section` - The name of the section the card belongs to, if any.
+/// * `details` - The `CardDetails` of the card.
+/// * `collected` - Whether the card is already collected.
+/// * `duplicates` - The number of duplicates of the card.
+#[derive(Debug, PartialEq)]
+pub struct ChecklistCard {
+    pub label: String,
+    pub section: Option<String>,
+    pub details: CardDetails,
+    pub collected: bool,
+    pub duplicates: u32,
+}
+
+/// A checklist parsed from a CSV or JSON file, describing every card of a collection.
+///
+/// # Fields
+/// * `name` - The name of the collection, if the file declares one.
+/// * `sections` - The names of the sections declared by the file, in order. Sections only named
+///   by cards are not listed here.
+/// * `cards` - The `ChecklistCard` entries, in file order.
+#[derive(Debug)]
+pub struct Checklist {
+    pub name: Option<String>,
+    pub sections: Vec<String>,
+    pub cards: Vec<ChecklistCard>,
+}
+
+/// The file formats a collection can be exported to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExportFormat {
+    Csv,
+    Json,
+}
+
+impl ExportFormat {
+    /// Returns the file extension of the format.
+    ///
+    /// # Returns
+    ///
+    /// * `&'static str` - The extension, without the leading dot.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "csv",
+            ExportFormat::Json => "json",
+        }
+    }
+}
+
+/// A validation error of a single entry of a checklist file.
+///
+/// # Fields
+/// * `line` - The line of the CSV row, or the position of the card in a JSON checklist, starting from 1.
+/// * `problem` - The `ChecklistProblem` found on the line.
+#[derive(Debug, PartialEq)]
+pub struct ChecklistError {
+    pub line: usize,
+    pub problem: ChecklistProblem,
+}
+
+/// The problems a checklist file can have.
+#[derive(Debug, PartialEq)]
+pub enum ChecklistProblem {
+    /// The header of a CSV checklist has no `label` column.
+    MissingLabelColumn,
+    /// An entry has an empty label.
+    MissingLabel,
+    /// The label of an entry was already used by an earlier entry.
+    RepeatedLabel(String),
+    /// The rarity of an entry is not one of `common`, `foil` or `legend`.
+    UnknownRarity(String),
+    /// The file could not be read as CSV or JSON, described by the parser.
+    Malformed(String),
+}
+
+impl fmt::Display for ChecklistError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Line {}: ", self.line)?;
+        match &self.problem {
+            ChecklistProblem::MissingLabelColumn => write!(f, "the \"label\" column is missing"),
+            ChecklistProblem::MissingLabel => write!(f, "the label is missing"),
+            ChecklistProblem::RepeatedLabel(label) => write!(f, "label \"{label}\" appears more than once"),
+            ChecklistProblem::UnknownRarity(rarity) => {
+                write!(f, "unknown rarity \"{rarity}\" (common, foil or legend)")
+            }
+            ChecklistProblem::Malformed(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+/// The columns of a CSV checklist, in the order `to_csv` writes them.
+const CSV_COLUMNS: [&str; 8] = ["label", "name", "category", "section", "rarity", "notes", "collected", "duplicates"];
+
+/// A card entry as written in a checklist file, before validation.
+/// Only the label is required, every other column may be left out.
+#[derive(Debug, Deserialize, Serialize)]
+struct RawEntry {
+    label: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    category: String,
+    #[serde(default)]
+    section: String,
+    #[serde(default)]
+    rarity: String,
+    #[serde(default)]
+    notes: String,
+    #[serde(default)]
+    collected: bool,
+    #[serde(default)]
+    duplicates: u32,
+}
+
+/// The layout of a JSON checklist.
+#[derive(Debug, Deserialize, Serialize)]
+struct RawChecklist {
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    sections: Vec<String>,
+    cards: Vec<RawEntry>,
+}
+
+impl From<&ChecklistCard> for RawEntry {
+    fn from(card: &ChecklistCard) -> Self {
+        Self {
+            label: card.label.clone(),
+            name: card.details.name.clone(),
+            category: card.details.category.clone(),
+            section: card.section.clone().unwrap_or_default(),
+            rarity: card.details.rarity.as_str().to_string(),
+            notes: card.details.notes.clone(),
+            collected: card.collected,
+            duplicates: card.duplicates,
+        }
+    }
+}
+
+/// Parses a CSV checklist. The first row must be a header naming the columns,
+/// out of `label`, `name`, `category`, `section`, `rarity`, `notes`, `collected` and `duplicates`,
+/// in any order. Only `label` is required.
+///
+/// # Arguments
+///
+/// * `text` - A string slice holding the contents of the CSV file.
+///
+/// # Returns
+///
+/// * `Result<Checklist, Vec<ChecklistError>>` - The checklist, or every problem found in the file.
+pub fn parse_csv(text: &str) -> Result<Checklist, Vec<ChecklistError>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .from_reader(text.as_bytes());
+
+    let headers = match reader.headers() {
+        Ok(headers) if headers.iter().any(|header| header == "label") => headers.clone(),
+        Ok(_) => return Err(vec![ChecklistError { line: 1, problem: ChecklistProblem::MissingLabelColumn }]),
+        Err(err) => return Err(vec![ChecklistError { line: 1, problem: ChecklistProblem::Malformed(err.to_string()) }]),
+    };
+
+    let mut entries = vec![];
+    let mut errors = vec![];
+    for record in reader.records() {
+        let result = record.and_then(|record| {
+            let line = record.position().map_or(0, |position| position.line() as usize);
+            record.deserialize::<RawEntry>(Some(&headers)).map(|entry| (line, entry))
+        });
+
+        match result {
+            Ok(entry) => entries.push(entry),
+            Err(err) => {
+                let line = err.position().map_or(0, |position| position.line() as usize);
+                errors.push(ChecklistError { line, problem: ChecklistProblem::Malformed(csv_error_message(&err)) });
+            }
+        }
+    }
+
+    validate(None, vec![], entries, errors)
+}
+
+/// Parses a JSON checklist of the form `{"name": "...", "cards": [{"label": "1", ...}, ...]}`,
+/// where the cards accept the same fields as the columns of a CSV checklist.
+///
+/// # Arguments
+///
+/// * `text` - A string slice holding the contents of the JSON file.
+///
+/// # Returns
+///
+/// * `Result<Checklist, Vec<ChecklistError>>` - The checklist, or every problem found in the file.
+pub fn parse_json(text: &str) -> Result<Checklist, Vec<ChecklistError>> {
+    let raw: RawChecklist = serde_json::from_str(text).map_err(|err| {
+        vec![ChecklistError { line: err.line(), problem: ChecklistProblem::Malformed(err.to_string()) }]
+    })?;
+
+    let entries = raw.cards.into_iter()
+        .enumerate()
+        .map(|(index, entry)| (index + 1, entry))
+        .collect();
+    let sections = raw.sections.into_iter()
+        .map(|section| section.trim().to_string())
+        .filter(|section| !section.is_empty())
+        .collect();
+    validate(raw.name, sections, entries, vec![])
+}
+
+/// Writes a collection as a CSV checklist, with one row per card and a header row naming
+/// every column, so the file can be imported back with `parse_csv`.
+/// The header is written even for a collection without cards.
+///
+/// # Arguments
+///
+/// * `cards` - A slice of the `ChecklistCard` entries of the collection.
+///
+/// # Returns
+///
+/// * `Result<String, StickereError>` - The contents of the CSV file.
+pub fn to_csv(cards: &[ChecklistCard]) -> Result<String, StickereError> {
+    let mut writer = csv::WriterBuilder::new().has_headers(false).from_writer(vec![]);
+    writer.write_record(CSV_COLUMNS)?;
+    for card in cards {
+        writer.serialize(RawEntry::from(card))?;
+    }
+
+    let bytes = writer.into_inner().map_err(|err| StickereError::Serialization(err.to_string()))?;
+    String::from_utf8(bytes).map_err(|err| StickereError::Serialization(err.to_string()))
+}
+
+/// Writes a collection as a JSON checklist, keeping its name, every section and the full state
+/// of every card, so the file can be imported back with `parse_json` without losing anything.
+///
+/// # Arguments
+///
+/// * `name` - A string slice representing the collection name.
+/// * `sections` - A slice of the section names of the collection, in order.
+/// * `cards` - A slice of the `ChecklistCard` entries of the collection.
+///
+/// # Returns
+///
+/// * `Result<String, StickereError>` - The contents of the JSON file.
+pub fn to_json(name: &str, sections: &[String], cards: &[ChecklistCard]) -> Result<String, StickereError> {
+    let raw = RawChecklist {
+        name: Some(name.to_string()),
+        sections: sections.to_vec(),
+        cards: cards.iter().map(RawEntry::from).collect(),
+    };
+
+    Ok(serde_json::to_string_pretty(&raw)?)
+}
+
+/// Validates the entries of a checklist, collecting every problem instead of stopping at the first one.
+///
+/// # Arguments
+///
+/// * `name` - The name of the collection declared by the file, if any.
+/// * `sections` - The names of the sections declared by the file.
+/// * `entries` - The raw entries, each with its line in the file.
+/// * `errors` - The problems already found while reading the file.
+///
+/// # Returns
+///
+/// * `Result<Checklist, Vec<ChecklistError>>` - The checklist, or every problem found in the file.
+fn validate(name: Option<String>, sections: Vec<String>, entries: Vec<(usize, RawEntry)>,
+            mut errors: Vec<ChecklistError>) -> Result<Checklist, Vec<ChecklistError>> {
+    let mut labels = HashSet::new();
+    let mut cards = vec![];
+
+    for (line, entry) in entries {
+        let label = entry.label.trim().to_string();
+        if label.is_empty() {
+            errors.push(ChecklistError { line, problem: ChecklistProblem::MissingLabel });
+            continue;
+        }
+        if !labels.insert(label.clone()) {
+            errors.push(ChecklistError { line, problem: ChecklistProblem::RepeatedLabel(label) });
+            continue;
+        }
+
+        let rarity = if entry.rarity.trim().is_empty() {
+            Rarity::Common
+        } else {
+            match Rarity::parse(&entry.rarity) {
+                Some(rarity) => rarity,
+                None => {
+                    let problem = ChecklistProblem::UnknownRarity(entry.rarity.trim().to_string());
+                    errors.push(ChecklistError { line, problem });
+                    continue;
+                }
+            }
+        };
+
+        let section = entry.section.trim();
+        cards.push(ChecklistCard {
+            label,
+            section: (!section.is_empty()).then(|| section.to_string()),
+            details: CardDetails {
+                name: entry.name.trim().to_string(),
+                category: entry.category.trim().to_string(),
+                rarity,
+                notes: entry.notes.trim().to_string(),
+            },
+            collected: entry.collected,
+            duplicates: entry.duplicates,
+        });
+    }
+
+    if errors.is_empty() {
+        Ok(Checklist { name, sections, cards })
+    } else {
+        errors.sort_by_key(|error| error.line);
+        Err(errors)
+    }
+}
+
+/// Describes a CSV error without the position, which is reported separately.
+fn csv_error_message(err: &csv::Error) -> String {
+    match err.kind() {
+        csv::ErrorKind::Deserialize { err, .. } => err.to_string(),
+        _ => err.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_columns_can_be_in_any_order() {
+        let checklist = parse_csv("section,label,rarity,name\n\
+                                   Stadiums,00,foil,Logo\n\
+                                   Team Brazil,BRA 1,,Alisson\n").unwrap();
+
+        assert_eq!(checklist.cards.len(), 2);
+        assert_eq!(checklist.cards[0].label, "00");
+        assert_eq!(checklist.cards[0].section.as_deref(), Some("Stadiums"));
+        assert_eq!(checklist.cards[0].details.rarity, Rarity::Foil);
+        assert_eq!(checklist.cards[1].details.name, "Alisson");
+        assert_eq!(checklist.cards[1].details.rarity, Rarity::Common);
+    }
+
+    #[test]
+    fn csv_errors_are_reported_per_line() {
+        let errors = parse_csv("label,rarity\n\
+                                1,common\n\
+                                ,common\n\
+                                1,foil\n\
+                                2,shiny\n").unwrap_err();
+
+        let lines: Vec<usize> = errors.iter().map(|error| error.line).collect();
+        assert_eq!(lines, [3, 4, 5]);
+    }
+
+    #[test]
+    fn json_checklist_keeps_name_and_state() {
+        let checklist = parse_json(r#"{"name": "Euro 2024", "cards": [
+            {"label": "1", "collected": true, "duplicates": 2},
+            {"label": "2", "rarity": "legend", "notes": "signed"}
+        ]}"#).unwrap();
+
+        assert_eq!(checklist.name.as_deref(), Some("Euro 2024"));
+        assert!(checklist.cards[0].collected);
+        assert_eq!(checklist.cards[0].duplicates, 2);
+        assert_eq!(checklist.cards[1].details.rarity, Rarity::Legend);
+        assert_eq!(checklist.cards[1].details.notes, "signed");
+    }
+
+    #[test]
+    fn exported_json_imports_back_unchanged() {
+        let cards = vec![
+            ChecklistCard {
+                label: "FWC 1".to_string(),
+                section: Some("Intro".to_string()),
+                details: CardDetails {
+                    name: "Trophy".to_string(),
+                    category: "FIFA".to_string(),
+                    rarity: Rarity::Foil,
+                    notes: "shiny, \"gold\"".to_string(),
+                },
+                collected: true,
+                duplicates: 3,
+            },
+            ChecklistCard {
+                label: "BRA 1".to_string(),
+                section: None,
+                details: CardDetails::default(),
+                collected: false,
+                duplicates: 0,
+            },
+        ];
+        let sections = vec!["Intro".to_string(), "Empty".to_string()];
+
+        let checklist = parse_json(&to_json("World Cup", &sections, &cards).unwrap()).unwrap();
+        assert_eq!(checklist.name.as_deref(), Some("World Cup"));
+        assert_eq!(checklist.sections, sections);
+        assert_eq!(checklist.cards, cards);
+
+        let checklist = parse_csv(&to_csv(&cards).unwrap()).unwrap();
+        assert_eq!(checklist.cards, cards);
+    }
+
+    #[test]
+    fn exported_empty_collection_imports_back_empty() {
+        let csv = to_csv(&[]).unwrap();
+        assert_eq!(csv, "label,name,category,section,rarity,notes,collected,duplicates\n");
+        assert!(parse_csv(&csv).unwrap().cards.is_empty());
+
+        let sections = vec!["Intro".to_string()];
+        let checklist = parse_json(&to_json("World Cup", &sections, &[]).unwrap()).unwrap();
+        assert_eq!(checklist.name.as_deref(), Some("World Cup"));
+        assert_eq!(checklist.sections, sections);
+        assert!(checklist.cards.is_empty());
+    }
+}
@@ -0,0 +1,75 @@
+use crate::card::Card;
+
+/// Represents a collection of cards.
+///
+/// # Fields
+/// * `id` - A `u32` representing the unique identifier of the collection.
+/// * `name` - A `String` representing the name of the collection.
+/// * `collected` - The number of collected cards, shown as the progress of the collection.
+/// * `total` - The total number of cards in the collection.
+#[derive(Debug, PartialEq)]
+pub struct Collection {
+    pub id: u32,
+    name: String,
+    collected: usize,
+    total: usize,
+}
+
+impl Collection {
+    /// Creates a new `Collection` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - A `u32` representing the unique identifier of the collection.
+    /// * `name` - A `String` representing the name of the collection.
+    /// * `collected` - The number of collected cards.
+    /// * `total` - The total number of cards in the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new instance of `Collection`.
+    pub fn new(id: u32, name: String, collected: usize, total: usize) -> Self {
+        Self { id, name, collected, total }
+    }
+
+    /// Returns the name of the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `&str` - The name of the collection.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the progress of the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `(usize, usize)` - The number of collected cards and the total number of cards.
+    pub fn progress(&self) -> (usize, usize) {
+        (self.collected, self.total)
+    }
+
+    /// Updates the progress of the collection from its cards.
+    ///
+    /// # Arguments
+    ///
+    /// * `cards` - A slice of the `Card` objects of the collection.
+    pub fn update_progress(&mut self, cards: &[Card]) {
+        self.collected = cards.iter().filter(|card| card.checked).count();
+        self.total = cards.len();
+    }
+}
+
+/// Represents a collection in the trash.
+///
+/// # Fields
+/// * `id` - A `u32` representing the unique identifier of the collection.
+/// * `name` - A `String` representing the name of the collection.
+/// * `deleted_at` - A `String` representing the local date and time of the deletion, as `YYYY-MM-DD HH:MM:SS`.
+#[derive(Debug, Clone)]
+pub struct DeletedCollection {
+    pub id: u32,
+    pub name: String,
+    pub deleted_at: String,
+}
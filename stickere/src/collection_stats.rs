@@ -0,0 +1,113 @@
+use crate::card::Card;
+use crate::section::Section;
+
+/// Completion statistics of a collection.
+///
+/// # Fields
+/// * `collected` - The number of collected cards.
+/// * `total` - The total number of cards.
+/// * `duplicates` - The total number of duplicates, over all the cards.
+/// * `sections` - The name, number of collected cards and total number of cards of every section,
+///   followed by the cards without a section, with no name, if there are any.
+#[derive(Debug, PartialEq)]
+pub struct CollectionStats {
+    pub collected: usize,
+    pub total: usize,
+    pub duplicates: u32,
+    pub sections: Vec<(Option<String>, usize, usize)>,
+}
+
+impl CollectionStats {
+    /// Computes the statistics of a collection from its cards and sections.
+    ///
+    /// # Arguments
+    ///
+    /// * `cards` - A slice of the `Card` objects of the collection.
+    /// * `sections` - A slice of the `Section` objects of the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics of the collection.
+    pub fn new(cards: &[Card], sections: &[Section]) -> Self {
+        let mut section_stats: Vec<_> = sections.iter()
+            .map(|section| {
+                let (collected, total) = Section::completion(Some(section.id), cards);
+                (Some(section.name.clone()), collected, total)
+            })
+            .collect();
+
+        if !sections.is_empty() {
+            let (collected, total) = Section::completion(None, cards);
+            if total > 0 {
+                section_stats.push((None, collected, total));
+            }
+        }
+
+        Self {
+            collected: cards.iter().filter(|card| card.checked).count(),
+            total: cards.len(),
+            duplicates: cards.iter().map(|card| card.duplicates).sum(),
+            sections: section_stats,
+        }
+    }
+
+    /// Counts the cards still missing from the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `usize` - The number of cards that are not collected.
+    pub fn missing(&self) -> usize {
+        self.total - self.collected
+    }
+
+    /// Computes the completed share of the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `f32` - The share of collected cards between 0 and 1, which is 0 for a collection without cards.
+    pub fn completion(&self) -> f32 {
+        fraction(self.collected, self.total)
+    }
+}
+
+/// Divides `part` by `total`, treating an empty total as no progress.
+pub fn fraction(part: usize, total: usize) -> f32 {
+    if total == 0 { 0.0 } else { part as f32 / total as f32 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card_details::CardDetails;
+
+    fn card(label: &str, checked: bool, duplicates: u32, section_id: Option<u32>) -> Card {
+        Card::new(label.to_string(), 0, checked, duplicates, section_id, CardDetails::default())
+    }
+
+    #[test]
+    fn stats_count_cards_duplicates_and_sections() {
+        let cards = [
+            card("1", true, 2, Some(1)),
+            card("2", false, 0, Some(1)),
+            card("3", true, 1, Some(2)),
+            card("4", false, 0, None),
+        ];
+        let sections = [Section::new(1, "Stadioane".to_string()), Section::new(2, "Romania".to_string())];
+
+        let stats = CollectionStats::new(&cards, &sections);
+
+        assert_eq!((stats.collected, stats.total, stats.duplicates, stats.missing()), (2, 4, 3, 2));
+        assert_eq!(stats.completion(), 0.5);
+        assert_eq!(stats.sections, [(Some("Stadioane".to_string()), 1, 2),
+                                    (Some("Romania".to_string()), 1, 1),
+                                    (None, 0, 1)]);
+    }
+
+    #[test]
+    fn empty_collection_has_no_progress() {
+        let stats = CollectionStats::new(&[], &[]);
+
+        assert_eq!(stats.completion(), 0.0);
+        assert!(stats.sections.is_empty());
+    }
+}
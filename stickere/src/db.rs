@@ -0,0 +1,935 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::fs;
+use rusqlite::backup::Progress;
+use rusqlite::{ffi, params, Connection, DatabaseName, OptionalExtension, Row};
+
+use crate::backup::{self, Backup};
+
+use crate::card::Card;
+use crate::card_details::CardDetails;
+use crate::checklist::ChecklistCard;
+use crate::collection::{Collection, DeletedCollection};
+use crate::error::StickereError;
+use crate::label;
+use crate::migrations;
+use crate::pack::{Pack, PackSummary};
+use crate::section::Section;
+use crate::storage::{Snapshot, Storage};
+use crate::trade::Trade;
+
+/// The columns of a card read by `DbHandler::card_from_row`, in order.
+const CARD_COLUMNS: &str = "id, card_number, collected, duplicates, section_id, \
+                            coalesce(name, ''), coalesce(category, ''), rarity, coalesce(notes, '')";
+
+/// A struct that handles database operations, storing the collections in an SQLite database.
+/// A backup of the database file is taken when it is opened and before collections lose data,
+/// keeping the last `backup::MAX_BACKUPS` of them.
+pub struct DbHandler {
+    connection: Connection,
+    db_path: Option<PathBuf>,
+}
+
+impl DbHandler {
+    /// Creates a new `DbHandler` instance and migrates the database to the latest schema.
+    /// An existing database file is backed up before it is migrated, unless it is unchanged since its last backup.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_path` - The path to the database file, or `:memory:` for a database that is not saved.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<DbHandler, StickereError>` - A new instance of `DbHandler` or the error that prevented opening it.
+    pub fn new(db_path: impl AsRef<Path>) -> Result<DbHandler, StickereError> {
+        let db_path = db_path.as_ref();
+        if let Some(parent_dir) = db_path.parent() {
+            // Check if the directory exists
+            if !parent_dir.exists() {
+                // Create the directory if it doesn't exist
+                fs::create_dir_all(parent_dir)?;
+            }
+        }
+
+        let existing = fs::metadata(db_path).is_ok_and(|metadata| metadata.len() > 0);
+        let backed_up = existing && backup::is_up_to_date(db_path, &backup::backup_dir(db_path))?;
+
+        let mut db_handler = Self {
+            connection: Connection::open(db_path)?,
+            db_path: (db_path != Path::new(":memory:")).then(|| db_path.to_path_buf()),
+        };
+        if existing && !backed_up {
+            db_handler.take_backup()?;
+        }
+        db_handler.migrate()?;
+        Ok(db_handler)
+    }
+
+    /// Migrates the database to the latest schema and enforces foreign keys, which the migrations turn off,
+    /// so the cards, sections, trades and packs of a purged collection are removed with it.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn migrate(&mut self) -> Result<(), StickereError> {
+        migrations::migrate(&mut self.connection)?;
+        self.connection.pragma_update(None, "foreign_keys", true)?;
+        Ok(())
+    }
+
+    /// Copies the database to a new backup file, named after the current local time, using the online backup API
+    /// of SQLite, and removes the backups older than the last `backup::MAX_BACKUPS`.
+    /// Nothing is done for a database that is not saved to a file.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn take_backup(&self) -> Result<(), StickereError> {
+        self.write_backup()?;
+        self.prune_backups()
+    }
+
+    /// Copies the database to a new backup file, named after the current local time, without removing older backups,
+    /// and records the state of the database file so an unchanged file is not backed up again when it is opened.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn write_backup(&self) -> Result<(), StickereError> {
+        let Some(db_path) = &self.db_path else {
+            return Ok(());
+        };
+        let dir = backup::backup_dir(db_path);
+
+        let timestamp: String = self.connection
+            .query_row("SELECT strftime('%Y-%m-%d %H:%M:%f', 'now', 'localtime')", [], |row| row.get(0))?;
+        fs::create_dir_all(&dir)?;
+        self.connection.backup(DatabaseName::Main, backup::backup_path(&dir, &timestamp), None)?;
+        backup::record_state(db_path, &dir)
+    }
+
+    /// Removes the backups older than the last `backup::MAX_BACKUPS`.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn prune_backups(&self) -> Result<(), StickereError> {
+        match self.backup_dir() {
+            Some(dir) => backup::prune(&dir, backup::MAX_BACKUPS),
+            None => Ok(()),
+        }
+    }
+
+    /// Returns the directory holding the backups of the database.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<PathBuf>` - The directory of the backups, `None` for a database that is not saved to a file.
+    fn backup_dir(&self) -> Option<PathBuf> {
+        self.db_path.as_deref().map(backup::backup_dir)
+    }
+
+    /// Generates cards for a specific collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    /// * `size` - A `u32` representing the number of cards to generate.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn generate_cards(&mut self, collection_id: u32, size: u32) -> Result<(), StickereError> {
+        let tx = self.connection.transaction()?;
+
+        {
+            let mut stmt = tx.prepare("INSERT INTO cards (collection_id, card_number, collected) \
+                                                     VALUES (?1, ?2, false)")?;
+
+            for label in 1..=size {
+                stmt.execute((collection_id, label.to_string()))?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Reads a card from a row whose last columns are `CARD_COLUMNS`.
+    ///
+    /// # Arguments
+    ///
+    /// * `row` - A reference to the `Row` holding the card.
+    ///
+    /// # Returns
+    ///
+    /// * `rusqlite::Result<Card>` - The card read from the row.
+    fn card_from_row(row: &Row) -> rusqlite::Result<Card> {
+        let first = row.as_ref().column_count() - 9;
+
+        Ok(Card::new(
+            row.get(first + 1)?,
+            row.get(first)?,
+            row.get(first + 2)?,
+            row.get(first + 3)?,
+            row.get(first + 4)?,
+            CardDetails {
+                name: row.get(first + 5)?,
+                category: row.get(first + 6)?,
+                rarity: row.get(first + 7)?,
+                notes: row.get(first + 8)?,
+            },
+        ))
+    }
+
+    /// Turns the violation of the unique label of the cards of a collection into a `DuplicateLabel` error.
+    ///
+    /// # Arguments
+    ///
+    /// * `err` - The `rusqlite::Error` returned by the statement writing the label.
+    /// * `label` - A string slice representing the label that was written.
+    ///
+    /// # Returns
+    ///
+    /// * `StickereError` - The error to report.
+    fn label_error(err: rusqlite::Error, label: &str) -> StickereError {
+        match err.sqlite_error() {
+            Some(error) if error.extended_code == ffi::SQLITE_CONSTRAINT_UNIQUE => {
+                StickereError::DuplicateLabel(label.to_string())
+            }
+            _ => StickereError::Database(err),
+        }
+    }
+
+    /// Looks up a card of a collection by its label.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the `Connection`, or the open transaction, to query.
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    /// * `label` - A string slice representing the label of the card.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(u32, bool, u32), StickereError>` - The identifier, collected status and duplicates of the card.
+    fn find_card(conn: &Connection, collection_id: u32, label: &str) -> Result<(u32, bool, u32), StickereError> {
+        conn.query_row("SELECT id, collected, duplicates FROM cards WHERE collection_id = ?1 AND card_number = ?2",
+                       params![collection_id, label],
+                       |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .optional()?
+            .ok_or_else(|| StickereError::CardNotFound(label.to_string()))
+    }
+
+    /// Adds a received copy of a card to the collection: the card is marked as collected,
+    /// or gains a duplicate if it was already collected.
+    ///
+    /// # Arguments
+    ///
+    /// * `conn` - A reference to the `Connection`, or the open transaction, to update.
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    /// * `label` - A string slice representing the label of the card.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, StickereError>` - `true` if the card was not collected before.
+    fn receive_card(conn: &Connection, collection_id: u32, label: &str) -> Result<bool, StickereError> {
+        let (card_id, collected, _) = Self::find_card(conn, collection_id, label)?;
+        if collected {
+            conn.execute("UPDATE cards SET duplicates = duplicates + 1 WHERE id = ?1", [card_id])?;
+        } else {
+            conn.execute("UPDATE cards SET collected = true WHERE id = ?1", [card_id])?;
+        }
+        Ok(!collected)
+    }
+}
+
+impl Storage for DbHandler {
+    fn get_collections(&self) -> Result<Vec<Collection>, StickereError> {
+        let mut stmt = self.connection
+            .prepare("SELECT id, name, \
+                          (SELECT count(*) FROM cards WHERE collection_id = collections.id AND collected), size \
+                      FROM collections WHERE deleted_at IS NULL ORDER BY id")?;
+
+        let iter = stmt.query_map([], |row| {
+            Ok(Collection::new(
+                row.get(0)?,
+                row.get(1)?,
+                row.get(2)?,
+                row.get(3)?)
+            )
+        })?;
+
+        Ok(iter.collect::<Result<_, _>>()?)
+    }
+
+    fn get_cards_from_collection(&self, id: u32) -> Result<Vec<Card>, StickereError> {
+        let mut stmt = self.connection
+            .prepare(&format!("SELECT {CARD_COLUMNS} FROM cards WHERE collection_id = ?1"))?;
+
+        let iter = stmt.query_map([id], Self::card_from_row)?;
+
+        let mut cards: Vec<Card> = iter.collect::<Result<_, _>>()?;
+        cards.sort_by(|a, b| label::natural_cmp(&a.label, &b.label));
+        Ok(cards)
+    }
+
+    fn update_card(&mut self, id: u32, status: bool) -> Result<(), StickereError> {
+        let mut stmt = self.connection
+            .prepare("UPDATE cards SET collected = ?1 WHERE id = ?2 ")?;
+
+        stmt.execute((status, id))?;
+        Ok(())
+    }
+
+    fn add_collection(&mut self, name: &str, size: u32) -> Result<u32, StickereError> {
+        let last_id: u32 = {
+            // The size is counted up by the database as the cards are generated.
+            let mut stmt = self.connection
+                .prepare("INSERT INTO collections (name, size) VALUES (?1, 0)")?;
+            stmt.execute([name])?;
+
+            let mut stmt = self.connection
+                .prepare("SELECT last_insert_rowid()")?;
+
+            stmt.query_row([], |row| {
+                row.get(0)
+            })?
+        };
+        self.generate_cards(last_id, size)?;
+        Ok(last_id)
+    }
+
+    fn rename_collection(&mut self, collection_id: u32, name: &str) -> Result<(), StickereError> {
+        self.connection.execute("UPDATE collections SET name = ?1 WHERE id = ?2", params![name, collection_id])?;
+        Ok(())
+    }
+
+    fn resize_collection(&mut self, collection_id: u32, size: u32) -> Result<(), StickereError> {
+        let cards = self.get_cards_from_collection(collection_id)?;
+        let size = size as usize;
+        if let Some(card) = cards.iter().skip(size)
+            .find(|card| card.checked || card.duplicates > 0 || !card.details.is_empty()) {
+            return Err(StickereError::CardNotEmpty(card.label.clone()));
+        }
+        if size < cards.len() {
+            self.take_backup()?;
+        }
+        let mut labels: Vec<String> = self.connection
+            .prepare("SELECT card_number FROM cards WHERE collection_id = ?1 ORDER BY id")?
+            .query_map([collection_id], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+
+        let tx = self.connection.transaction()?;
+
+        if size > cards.len() {
+            let mut stmt = tx.prepare("INSERT INTO cards (collection_id, card_number, collected) VALUES (?1, ?2, false)")?;
+            for _ in cards.len()..size {
+                let label = label::next_label(&labels);
+                stmt.execute(params![collection_id, label])?;
+                labels.push(label);
+            }
+        } else {
+            for card in &cards[size..] {
+                tx.execute("DELETE FROM cards WHERE id = ?1", [card.id])?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    fn delete_collection(&mut self, collection_id: u32) -> Result<(), StickereError> {
+        self.take_backup()?;
+        self.connection.execute("UPDATE collections SET deleted_at = datetime('now') WHERE id = ?1", [collection_id])?;
+        Ok(())
+    }
+
+    fn restore_collection(&mut self, collection_id: u32) -> Result<(), StickereError> {
+        self.connection.execute("UPDATE collections SET deleted_at = NULL WHERE id = ?1", [collection_id])?;
+        Ok(())
+    }
+
+    fn purge_collection(&mut self, collection_id: u32) -> Result<(), StickereError> {
+        self.take_backup()?;
+        self.connection.execute("DELETE FROM collections WHERE id = ?1 AND deleted_at IS NOT NULL", [collection_id])?;
+        Ok(())
+    }
+
+    fn get_deleted_collections(&self) -> Result<Vec<DeletedCollection>, StickereError> {
+        let mut stmt = self.connection
+            .prepare("SELECT id, name, datetime(deleted_at, 'localtime') FROM collections \
+                      WHERE deleted_at IS NOT NULL ORDER BY deleted_at DESC, id DESC")?;
+
+        let iter = stmt.query_map([], |row| {
+            Ok(DeletedCollection {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                deleted_at: row.get(2)?,
+            })
+        })?;
+
+        Ok(iter.collect::<Result<_, _>>()?)
+    }
+
+    fn get_collection_name(&self, collection_id: u32) -> Result<String, StickereError> {
+        let mut stmt = self.connection
+            .prepare("SELECT name FROM collections WHERE id = ?1")?;
+
+        Ok(stmt.query_row([collection_id], |row| {
+            row.get(0)
+        })?)
+    }
+
+    fn get_next_label_for_collection(&self, collection_id: u32) -> Result<String, StickereError> {
+        let mut stmt = self.connection.prepare(
+            "SELECT card_number FROM cards WHERE collection_id = ?1 ORDER BY id"
+        )?;
+
+        let labels: Vec<String> = stmt.query_map([collection_id], |row| row.get(0))?
+            .collect::<Result<_, _>>()?;
+        Ok(label::next_label(&labels))
+    }
+
+    fn add_card(&mut self, card_number: &str, collection_id: u32) -> Result<u32, StickereError> {
+        self.connection.execute("INSERT INTO cards (collection_id, card_number, collected) VALUES (?1,?2,?3)",
+                                params![collection_id,card_number,0])
+            .map_err(|err| Self::label_error(err, card_number))?;
+
+        let mut stmt = self.connection
+            .prepare("SELECT last_insert_rowid()")?;
+
+        Ok(stmt.query_row([], |row| {
+            row.get(0)
+        })?)
+    }
+
+    fn update_card_duplicates(&mut self, card: &mut Card) -> Result<(), StickereError> {
+        self.connection.execute("UPDATE cards SET duplicates = ?1 WHERE id = ?2", [card.duplicates, card.id])?;
+        Ok(())
+    }
+
+    fn set_card_duplicates(&mut self, card_id: u32, duplicates: u32) -> Result<(), StickereError> {
+        self.connection.execute("UPDATE cards SET duplicates = ?1 WHERE id = ?2", [duplicates, card_id])?;
+        Ok(())
+    }
+
+    fn update_card_label(&mut self, card_id: u32, label: &str) -> Result<(), StickereError> {
+        self.connection.execute("UPDATE cards SET card_number = ?1 WHERE id = ?2", params![label, card_id])
+            .map_err(|err| Self::label_error(err, label))?;
+        Ok(())
+    }
+
+    fn delete_card(&mut self, card_id: u32) -> Result<(), StickereError> {
+        self.connection.execute("DELETE FROM cards WHERE id = ?1", [card_id])?;
+        Ok(())
+    }
+
+    fn snapshot_card(&self, card_id: u32) -> Result<Snapshot, StickereError> {
+        self.connection.query_row(&format!("SELECT collection_id, {CARD_COLUMNS} FROM cards WHERE id = ?1"),
+                                  [card_id],
+                                  |row| Ok(Snapshot { collection_id: row.get(0)?, card: Self::card_from_row(row)? }))
+            .optional()?
+            .ok_or_else(|| StickereError::CardNotFound(card_id.to_string()))
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) -> Result<(), StickereError> {
+        let card = &snapshot.card;
+        let details = &card.details;
+        self.connection.execute("INSERT INTO cards (id, collection_id, card_number, collected, duplicates, section_id, \
+                                 name, category, rarity, notes) \
+                                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, nullif(?7, ''), nullif(?8, ''), ?9, nullif(?10, ''))",
+                                params![card.id, snapshot.collection_id, card.label, card.checked, card.duplicates,
+                                        card.section_id, details.name, details.category, details.rarity, details.notes])
+            .map_err(|err| Self::label_error(err, &card.label))?;
+        Ok(())
+    }
+
+    fn update_card_details(&mut self, card: &Card) -> Result<(), StickereError> {
+        // Empty details are stored as `NULL`.
+        let details = &card.details;
+        self.connection.execute("UPDATE cards SET name = nullif(?1, ''), category = nullif(?2, ''), \
+                                 rarity = ?3, notes = nullif(?4, '') WHERE id = ?5",
+                                params![details.name, details.category, details.rarity, details.notes, card.id])?;
+        Ok(())
+    }
+
+    fn get_sections_from_collection(&self, collection_id: u32) -> Result<Vec<Section>, StickereError> {
+        let mut stmt = self.connection
+            .prepare("SELECT id, name FROM sections WHERE collection_id = ?1 ORDER BY id")?;
+
+        let iter = stmt.query_map([collection_id], |row| {
+            Ok(Section::new(row.get(0)?, row.get(1)?))
+        })?;
+
+        Ok(iter.collect::<Result<_, _>>()?)
+    }
+
+    fn add_section(&mut self, collection_id: u32, name: &str) -> Result<u32, StickereError> {
+        self.connection.execute("INSERT INTO sections (collection_id, name) VALUES (?1, ?2)",
+                                params![collection_id, name])?;
+
+        let mut stmt = self.connection
+            .prepare("SELECT last_insert_rowid()")?;
+
+        Ok(stmt.query_row([], |row| {
+            row.get(0)
+        })?)
+    }
+
+    fn assign_cards_to_section(&mut self, collection_id: u32, section_id: u32,
+                               first_label: &str, last_label: &str) -> Result<usize, StickereError> {
+        let cards = self.get_cards_from_collection(collection_id)?;
+        let tx = self.connection.transaction()?;
+        let mut moved = 0;
+
+        {
+            let mut stmt = tx.prepare("UPDATE cards SET section_id = ?1 WHERE id = ?2")?;
+
+            for card in cards.iter().filter(|card| {
+                label::natural_cmp(&card.label, first_label).is_ge() &&
+                    label::natural_cmp(&card.label, last_label).is_le()
+            }) {
+                stmt.execute((section_id, card.id))?;
+                moved += 1;
+            }
+        }
+        tx.commit()?;
+
+        Ok(moved)
+    }
+
+    fn import_collection(&mut self, name: &str, sections: &[String],
+                         cards: &[ChecklistCard]) -> Result<u32, StickereError> {
+        let tx = self.connection.transaction()?;
+
+        tx.execute("INSERT INTO collections (name, size) VALUES (?1, 0)", [name])?;
+        let collection_id = tx.last_insert_rowid() as u32;
+
+        {
+            let mut section_stmt = tx.prepare("INSERT INTO sections (collection_id, name) VALUES (?1, ?2)")?;
+            let mut card_stmt = tx.prepare(
+                "INSERT INTO cards (collection_id, card_number, collected, duplicates, section_id, \
+                 name, category, rarity, notes) \
+                 VALUES (?1, ?2, ?3, ?4, ?5, nullif(?6, ''), nullif(?7, ''), ?8, nullif(?9, ''))")?;
+            let mut section_ids: HashMap<&str, u32> = HashMap::new();
+
+            for section in sections {
+                if !section_ids.contains_key(section.as_str()) {
+                    let section_id = section_stmt.insert(params![collection_id, section])? as u32;
+                    section_ids.insert(section, section_id);
+                }
+            }
+
+            for card in cards {
+                let section_id = match card.section.as_deref() {
+                    Some(section) => match section_ids.get(section) {
+                        Some(&section_id) => Some(section_id),
+                        None => {
+                            let section_id = section_stmt.insert(params![collection_id, section])? as u32;
+                            section_ids.insert(section, section_id);
+                            Some(section_id)
+                        }
+                    },
+                    None => None,
+                };
+
+                let details = &card.details;
+                card_stmt.execute(params![collection_id, card.label, card.collected, card.duplicates, section_id,
+                                          details.name, details.category, details.rarity, details.notes])?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(collection_id)
+    }
+
+    fn set_collected(&mut self, collection_id: u32, labels: &[String], collected: bool) -> Result<(), StickereError> {
+        let tx = self.connection.transaction()?;
+
+        for label in labels {
+            let (card_id, _, _) = Self::find_card(&tx, collection_id, label)?;
+            tx.execute("UPDATE cards SET collected = ?1 WHERE id = ?2", params![collected, card_id])?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    fn change_duplicates(&mut self, collection_id: u32, changes: &[(String, i64)]) -> Result<(), StickereError> {
+        let tx = self.connection.transaction()?;
+
+        for (label, change) in changes {
+            let (card_id, _, duplicates) = Self::find_card(&tx, collection_id, label)?;
+            let duplicates = duplicates as i64 + change;
+            if duplicates < 0 {
+                return Err(StickereError::NoDuplicateToRemove(label.clone()));
+            }
+            tx.execute("UPDATE cards SET duplicates = ?1 WHERE id = ?2", params![duplicates, card_id])?;
+        }
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    fn record_trade(&mut self, collection_id: u32, partner: &str, given: &[String],
+                    received: &[String]) -> Result<u32, StickereError> {
+        let tx = self.connection.transaction()?;
+
+        tx.execute("INSERT INTO trades (collection_id, partner) VALUES (?1, ?2)", params![collection_id, partner])?;
+        let trade_id = tx.last_insert_rowid() as u32;
+
+        {
+            let mut record_stmt = tx.prepare("INSERT INTO trade_cards (trade_id, card_number, given) \
+                                              VALUES (?1, ?2, ?3)")?;
+
+            for label in given {
+                let (card_id, _, duplicates) = Self::find_card(&tx, collection_id, label)?;
+                if duplicates == 0 {
+                    return Err(StickereError::NoDuplicateToGive(label.clone()));
+                }
+                tx.execute("UPDATE cards SET duplicates = duplicates - 1 WHERE id = ?1", [card_id])?;
+                record_stmt.execute(params![trade_id, label, true])?;
+            }
+
+            for label in received {
+                Self::receive_card(&tx, collection_id, label)?;
+                record_stmt.execute(params![trade_id, label, false])?;
+            }
+        }
+        tx.commit()?;
+
+        Ok(trade_id)
+    }
+
+    fn open_pack(&mut self, collection_id: u32, labels: &[String]) -> Result<PackSummary, StickereError> {
+        let tx = self.connection.transaction()?;
+        let mut summary = PackSummary::default();
+
+        tx.execute("INSERT INTO packs (collection_id) VALUES (?1)", [collection_id])?;
+        let pack_id = tx.last_insert_rowid() as u32;
+
+        {
+            let mut record_stmt = tx.prepare("INSERT INTO pack_cards (pack_id, card_number, new) VALUES (?1, ?2, ?3)")?;
+
+            for label in labels {
+                let new = Self::receive_card(&tx, collection_id, label)?;
+                record_stmt.execute(params![pack_id, label, new])?;
+                if new {
+                    summary.new.push(label.clone());
+                } else {
+                    summary.duplicates.push(label.clone());
+                }
+            }
+        }
+        tx.commit()?;
+
+        Ok(summary)
+    }
+
+    fn get_trades_from_collection(&self, collection_id: u32) -> Result<Vec<Trade>, StickereError> {
+        let mut trade_stmt = self.connection
+            .prepare("SELECT id, partner, datetime(traded_at, 'localtime') FROM trades \
+                      WHERE collection_id = ?1 ORDER BY traded_at DESC, id DESC")?;
+        let mut card_stmt = self.connection
+            .prepare("SELECT card_number, given FROM trade_cards WHERE trade_id = ?1 ORDER BY rowid")?;
+
+        let trades = trade_stmt.query_map([collection_id], |row| {
+            Ok(Trade {
+                id: row.get(0)?,
+                partner: row.get(1)?,
+                traded_at: row.get(2)?,
+                given: vec![],
+                received: vec![],
+            })
+        })?.collect::<Result<Vec<_>, _>>()?;
+
+        trades.into_iter().map(|mut trade| {
+            for card in card_stmt.query_map([trade.id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?)))? {
+                let (label, given) = card?;
+                if given {
+                    trade.given.push(label);
+                } else {
+                    trade.received.push(label);
+                }
+            }
+            Ok(trade)
+        }).collect()
+    }
+
+    fn get_packs_from_collection(&self, collection_id: u32) -> Result<Vec<Pack>, StickereError> {
+        let mut pack_stmt = self.connection
+            .prepare("SELECT id, datetime(opened_at, 'localtime') FROM packs \
+                      WHERE collection_id = ?1 ORDER BY opened_at, id")?;
+        let mut card_stmt = self.connection
+            .prepare("SELECT card_number, new FROM pack_cards WHERE pack_id = ?1 ORDER BY rowid")?;
+
+        let packs = pack_stmt.query_map([collection_id], |row| {
+            Ok(Pack {
+                id: row.get(0)?,
+                opened_at: row.get(1)?,
+                summary: PackSummary::default(),
+            })
+        })?.collect::<Result<Vec<_>, _>>()?;
+
+        packs.into_iter().map(|mut pack| {
+            for card in card_stmt.query_map([pack.id], |row| Ok((row.get::<_, String>(0)?, row.get::<_, bool>(1)?)))? {
+                let (label, new) = card?;
+                if new {
+                    pack.summary.new.push(label);
+                } else {
+                    pack.summary.duplicates.push(label);
+                }
+            }
+            Ok(pack)
+        }).collect()
+    }
+
+    fn get_backups(&self) -> Result<Vec<Backup>, StickereError> {
+        match self.backup_dir() {
+            Some(dir) => backup::list(&dir),
+            None => Ok(vec![]),
+        }
+    }
+
+    fn restore_backup(&mut self, backup: &Backup) -> Result<(), StickereError> {
+        if !backup.path.is_file() {
+            return Err(StickereError::BackupNotFound(backup.path.display().to_string()));
+        }
+
+        // The backups are pruned only once restored, so the backup being restored cannot be pruned first.
+        self.write_backup()?;
+        self.connection.restore(DatabaseName::Main, &backup.path, None::<fn(Progress)>)?;
+        // Backups taken by an older version of the application are brought to the latest schema.
+        self.migrate()?;
+        self.prune_backups()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn collection_with_cards(size: u32) -> (DbHandler, u32) {
+        let mut db_handler = DbHandler::new(":memory:").unwrap();
+        let collection_id = db_handler.add_collection("Euro 2024", size).unwrap();
+        (db_handler, collection_id)
+    }
+
+    fn card<'a>(cards: &'a [Card], label: &str) -> &'a Card {
+        cards.iter().find(|card| card.label == label).unwrap()
+    }
+
+    #[test]
+    fn recorded_trade_updates_cards_and_history() {
+        let (mut db_handler, collection_id) = collection_with_cards(3);
+        let mut cards = db_handler.get_cards_from_collection(collection_id).unwrap();
+        cards[0].duplicates = 2;
+        db_handler.update_card_duplicates(&mut cards[0]).unwrap();
+        db_handler.update_card(cards[1].id, true).unwrap();
+
+        db_handler.record_trade(collection_id, "Ana", &["1".to_string()], &["2".to_string(), "3".to_string()])
+            .unwrap();
+
+        let cards = db_handler.get_cards_from_collection(collection_id).unwrap();
+        assert_eq!(card(&cards, "1").duplicates, 1);
+        assert_eq!((card(&cards, "2").checked, card(&cards, "2").duplicates), (true, 1));
+        assert_eq!((card(&cards, "3").checked, card(&cards, "3").duplicates), (true, 0));
+
+        let trades = db_handler.get_trades_from_collection(collection_id).unwrap();
+        assert_eq!(trades.len(), 1);
+        assert_eq!(trades[0].partner, "Ana");
+        assert_eq!(trades[0].given, ["1"]);
+        assert_eq!(trades[0].received, ["2", "3"]);
+    }
+
+    #[test]
+    fn invalid_trade_changes_nothing() {
+        let (mut db_handler, collection_id) = collection_with_cards(3);
+
+        let result = db_handler.record_trade(collection_id, "Ana", &["2".to_string()], &["3".to_string()]);
+        assert!(matches!(result, Err(StickereError::NoDuplicateToGive(label)) if label == "2"));
+
+        let result = db_handler.record_trade(collection_id, "Ana", &[], &["3".to_string(), "99".to_string()]);
+        assert!(matches!(result, Err(StickereError::CardNotFound(label)) if label == "99"));
+
+        let cards = db_handler.get_cards_from_collection(collection_id).unwrap();
+        assert!(cards.iter().all(|card| !card.checked && card.duplicates == 0));
+        assert!(db_handler.get_trades_from_collection(collection_id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn opened_pack_marks_new_cards_and_counts_duplicates() {
+        let (mut db_handler, collection_id) = collection_with_cards(3);
+        let cards = db_handler.get_cards_from_collection(collection_id).unwrap();
+        db_handler.update_card(card(&cards, "2").id, true).unwrap();
+
+        let pack = ["1", "2", "1"].map(String::from);
+        let summary = db_handler.open_pack(collection_id, &pack).unwrap();
+
+        assert_eq!(summary, PackSummary { new: vec!["1".to_string()], duplicates: vec!["2".to_string(), "1".to_string()] });
+        let cards = db_handler.get_cards_from_collection(collection_id).unwrap();
+        assert_eq!((card(&cards, "1").checked, card(&cards, "1").duplicates), (true, 1));
+        assert_eq!((card(&cards, "2").checked, card(&cards, "2").duplicates), (true, 1));
+        assert!(!card(&cards, "3").checked);
+
+        let result = db_handler.open_pack(collection_id, &["3".to_string(), "99".to_string()]);
+        assert!(matches!(result, Err(StickereError::CardNotFound(label)) if label == "99"));
+        let cards = db_handler.get_cards_from_collection(collection_id).unwrap();
+        assert!(!card(&cards, "3").checked);
+
+        let packs = db_handler.get_packs_from_collection(collection_id).unwrap();
+        assert_eq!(packs.len(), 1);
+        assert_eq!(packs[0].summary, summary);
+    }
+
+    #[test]
+    fn collections_report_their_progress() {
+        let (mut db_handler, collection_id) = collection_with_cards(4);
+        db_handler.add_collection("Empty", 0).unwrap();
+        db_handler.open_pack(collection_id, &["1".to_string(), "3".to_string()]).unwrap();
+        // Cards have a name of their own, which must not be mistaken for the name of their collection.
+        let mut card = db_handler.get_cards_from_collection(collection_id).unwrap().remove(0);
+        card.details.name = "Kylian Mbappe".to_string();
+        db_handler.update_card_details(&card).unwrap();
+
+        let collections = db_handler.get_collections().unwrap();
+        assert_eq!(collections, [Collection::new(collection_id, "Euro 2024".to_string(), 2, 4),
+                                 Collection::new(collection_id + 1, "Empty".to_string(), 0, 0)]);
+    }
+
+    #[test]
+    fn deleted_collections_go_through_the_trash() {
+        let (mut db_handler, collection_id) = collection_with_cards(2);
+
+        db_handler.delete_collection(collection_id).unwrap();
+        assert!(db_handler.get_collections().unwrap().is_empty());
+        assert_eq!(db_handler.get_deleted_collections().unwrap()[0].name, "Euro 2024");
+
+        db_handler.restore_collection(collection_id).unwrap();
+        assert_eq!(db_handler.get_collections().unwrap().len(), 1);
+        db_handler.purge_collection(collection_id).unwrap();
+        assert_eq!(db_handler.get_collections().unwrap().len(), 1);
+
+        db_handler.delete_collection(collection_id).unwrap();
+        db_handler.purge_collection(collection_id).unwrap();
+        assert!(db_handler.get_deleted_collections().unwrap().is_empty());
+        assert!(db_handler.get_cards_from_collection(collection_id).unwrap().is_empty());
+    }
+
+    #[test]
+    fn resizing_adds_and_removes_trailing_cards() {
+        let (mut db_handler, collection_id) = collection_with_cards(3);
+        db_handler.add_card("FWC 1", collection_id).unwrap();
+        let labels = |db_handler: &DbHandler| -> Vec<String> {
+            db_handler.get_cards_from_collection(collection_id).unwrap().into_iter().map(|card| card.label).collect()
+        };
+
+        db_handler.resize_collection(collection_id, 6).unwrap();
+        assert_eq!(labels(&db_handler), ["1", "2", "3", "FWC 1", "FWC 2", "FWC 3"]);
+        assert_eq!(db_handler.get_collections().unwrap(), [Collection::new(collection_id, "Euro 2024".to_string(), 0, 6)]);
+
+        let cards = db_handler.get_cards_from_collection(collection_id).unwrap();
+        db_handler.update_card(card(&cards, "FWC 1").id, true).unwrap();
+        let result = db_handler.resize_collection(collection_id, 2);
+        assert!(matches!(result, Err(StickereError::CardNotEmpty(label)) if label == "FWC 1"));
+        assert_eq!(labels(&db_handler).len(), 6);
+
+        db_handler.resize_collection(collection_id, 4).unwrap();
+        assert_eq!(labels(&db_handler), ["1", "2", "3", "FWC 1"]);
+        db_handler.rename_collection(collection_id, "Qatar 2022").unwrap();
+        assert_eq!(db_handler.get_collections().unwrap(), [Collection::new(collection_id, "Qatar 2022".to_string(), 1, 4)]);
+    }
+
+    #[test]
+    fn labels_are_unique_within_a_collection() {
+        let (mut db_handler, collection_id) = collection_with_cards(2);
+        let other_collection_id = db_handler.add_collection("Qatar 2022", 0).unwrap();
+
+        let result = db_handler.add_card("2", collection_id);
+        assert!(matches!(result, Err(StickereError::DuplicateLabel(label)) if label == "2"));
+        db_handler.add_card("2", other_collection_id).unwrap();
+
+        let card_id = db_handler.add_card("3", collection_id).unwrap();
+        let result = db_handler.update_card_label(card_id, "1");
+        assert!(matches!(result, Err(StickereError::DuplicateLabel(label)) if label == "1"));
+    }
+
+    #[test]
+    fn bulk_changes_apply_to_all_cards_or_none() {
+        let (mut db_handler, collection_id) = collection_with_cards(3);
+
+        db_handler.set_collected(collection_id, &["1".to_string(), "3".to_string()], true).unwrap();
+        db_handler.change_duplicates(collection_id, &[("1".to_string(), 2), ("3".to_string(), 1), ("3".to_string(), -1)])
+            .unwrap();
+
+        let result = db_handler.change_duplicates(collection_id, &[("1".to_string(), -1), ("2".to_string(), -1)]);
+        assert!(matches!(result, Err(StickereError::NoDuplicateToRemove(label)) if label == "2"));
+        let result = db_handler.set_collected(collection_id, &["2".to_string(), "4".to_string()], true);
+        assert!(matches!(result, Err(StickereError::CardNotFound(label)) if label == "4"));
+
+        let cards = db_handler.get_cards_from_collection(collection_id).unwrap();
+        let states: Vec<_> = cards.iter().map(|card| (card.checked, card.duplicates)).collect();
+        assert_eq!(states, [(true, 2), (false, 0), (true, 0)]);
+    }
+
+    #[test]
+    fn reopening_backs_up_only_a_changed_database() {
+        let dir = std::env::temp_dir().join(format!("stickere-db-reopen-{}", std::process::id()));
+        let db_path = dir.join("stick.db");
+        DbHandler::new(&db_path).unwrap().add_collection("Euro 2024", 3).unwrap();
+
+        drop(DbHandler::new(&db_path).unwrap());
+        let mut db_handler = DbHandler::new(&db_path).unwrap();
+        assert_eq!(db_handler.get_backups().unwrap().len(), 1);
+
+        db_handler.add_collection("Euro 2020", 1).unwrap();
+        drop(db_handler);
+        let db_handler = DbHandler::new(&db_path).unwrap();
+        assert_eq!(db_handler.get_backups().unwrap().len(), 2);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn backups_are_taken_before_data_is_lost_and_can_be_restored() {
+        let dir = std::env::temp_dir().join(format!("stickere-db-backups-{}", std::process::id()));
+        let db_path = dir.join("stick.db");
+
+        let mut db_handler = DbHandler::new(&db_path).unwrap();
+        assert!(db_handler.get_backups().unwrap().is_empty());
+        let collection_id = db_handler.add_collection("Euro 2024", 3).unwrap();
+        drop(db_handler);
+
+        drop(DbHandler::new(&db_path).unwrap());
+        let mut db_handler = DbHandler::new(&db_path).unwrap();
+        assert_eq!(db_handler.get_backups().unwrap().len(), 1, "an unchanged database is backed up once");
+        db_handler.delete_collection(collection_id).unwrap();
+        db_handler.purge_collection(collection_id).unwrap();
+        assert!(db_handler.get_collections().unwrap().is_empty());
+
+        let backups = db_handler.get_backups().unwrap();
+        assert_eq!(backups.len(), 3);
+        db_handler.restore_backup(&backups[1]).unwrap();
+        let collections = db_handler.get_collections().unwrap();
+        assert_eq!(collections.iter().map(|collection| collection.name()).collect::<Vec<_>>(), ["Euro 2024"]);
+        assert_eq!(db_handler.get_cards_from_collection(collection_id).unwrap().len(), 3);
+        assert_eq!(db_handler.get_backups().unwrap().len(), 4);
+
+        let last_card_id = db_handler.get_cards_from_collection(collection_id).unwrap()[2].id;
+        db_handler.update_card(last_card_id, true).unwrap();
+        assert!(db_handler.resize_collection(collection_id, 2).is_err());
+        assert_eq!(db_handler.get_backups().unwrap().len(), 4, "a refused shrink is not backed up");
+
+        db_handler.delete_collection(collection_id).unwrap();
+        db_handler.purge_collection(collection_id).unwrap();
+        let orphans: u32 = db_handler.connection.query_row("SELECT count(*) FROM cards", [], |row| row.get(0)).unwrap();
+        assert_eq!(orphans, 0);
+
+        for _ in 0..backup::MAX_BACKUPS {
+            db_handler.delete_collection(collection_id).unwrap();
+        }
+        assert_eq!(db_handler.get_backups().unwrap().len(), backup::MAX_BACKUPS);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,102 @@
+use std::fmt;
+
+/// The errors that can occur while working with the sticker database.
+#[derive(Debug)]
+pub enum StickereError {
+    /// The database directory could not be created or accessed.
+    Io(std::io::Error),
+    /// A query against the SQLite database failed.
+    Database(rusqlite::Error),
+    /// The database was written by a newer version of the application.
+    UnsupportedSchemaVersion(u32),
+    /// A collection could not be written in an export format.
+    Serialization(String),
+    /// No card with the given label exists in the collection.
+    CardNotFound(String),
+    /// A card was given away in a trade without having a duplicate of it.
+    NoDuplicateToGive(String),
+    /// A card that is collected, or has duplicates or details, would be removed by resizing its collection.
+    CardNotEmpty(String),
+    /// A card with the given label already exists in the collection.
+    DuplicateLabel(String),
+    /// A duplicate was removed from a card without duplicates.
+    NoDuplicateToRemove(String),
+    /// No collection with the given name or identifier exists.
+    CollectionNotFound(String),
+    /// A profile name that cannot be used as the name of its database file.
+    InvalidProfileName(String),
+    /// The backup to restore the database from does not exist.
+    BackupNotFound(String),
+    /// A label, range or duplicate change given as an argument could not be read.
+    InvalidArgument(String),
+}
+
+impl fmt::Display for StickereError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            StickereError::Io(err) => write!(f, "File system error: {err}"),
+            StickereError::Database(err) => write!(f, "Database error: {err}"),
+            StickereError::UnsupportedSchemaVersion(version) => {
+                write!(f, "Database schema version {version} is newer than this application supports")
+            }
+            StickereError::Serialization(message) => write!(f, "Export error: {message}"),
+            StickereError::CardNotFound(label) => write!(f, "Card {label} does not exist in this collection"),
+            StickereError::NoDuplicateToGive(label) => write!(f, "Card {label} has no duplicate to give away"),
+            StickereError::CardNotEmpty(label) => {
+                write!(f, "Card {label} is collected or has duplicates or details and cannot be removed")
+            }
+            StickereError::DuplicateLabel(label) => write!(f, "Card {label} already exists in this collection"),
+            StickereError::NoDuplicateToRemove(label) => write!(f, "Card {label} has no duplicate to remove"),
+            StickereError::CollectionNotFound(name) => write!(f, "Collection {name} does not exist"),
+            StickereError::InvalidProfileName(name) => {
+                write!(f, "Profile name \"{name}\" may only contain letters, digits, spaces, - and _")
+            }
+            StickereError::BackupNotFound(path) => write!(f, "Backup {path} does not exist"),
+            StickereError::InvalidArgument(message) => write!(f, "Invalid argument: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for StickereError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            StickereError::Io(err) => Some(err),
+            StickereError::Database(err) => Some(err),
+            StickereError::UnsupportedSchemaVersion(_)
+            | StickereError::Serialization(_)
+            | StickereError::CardNotFound(_)
+            | StickereError::NoDuplicateToGive(_)
+            | StickereError::CardNotEmpty(_)
+            | StickereError::DuplicateLabel(_)
+            | StickereError::NoDuplicateToRemove(_)
+            | StickereError::CollectionNotFound(_)
+            | StickereError::InvalidProfileName(_)
+            | StickereError::BackupNotFound(_)
+            | StickereError::InvalidArgument(_) => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for StickereError {
+    fn from(err: std::io::Error) -> Self {
+        StickereError::Io(err)
+    }
+}
+
+impl From<rusqlite::Error> for StickereError {
+    fn from(err: rusqlite::Error) -> Self {
+        StickereError::Database(err)
+    }
+}
+
+impl From<csv::Error> for StickereError {
+    fn from(err: csv::Error) -> Self {
+        StickereError::Serialization(err.to_string())
+    }
+}
+
+impl From<serde_json::Error> for StickereError {
+    fn from(err: serde_json::Error) -> Self {
+        StickereError::Serialization(err.to_string())
+    }
+}
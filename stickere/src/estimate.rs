@@ -0,0 +1,126 @@
+/// The expected number of packs, and the money, needed to finish a collection.
+///
+/// # Fields
+/// * `packs` - The expected number of packs still to buy.
+/// * `money` - The expected money still to spend, on packs and on ordered stickers.
+#[derive(Debug, PartialEq)]
+pub struct Cost {
+    pub packs: f64,
+    pub money: f64,
+}
+
+/// Estimates what finishing a collection by buying packs costs, following the coupon collector's problem:
+/// every sticker of a pack is assumed to be any sticker of the album with the same probability.
+/// With `n` stickers in the album, getting from `m` missing stickers down to `l` takes
+/// `n * (H(m) - H(l))` stickers on average, where `H` are the harmonic numbers.
+///
+/// # Fields
+/// * `total` - The number of stickers in the album.
+/// * `missing` - The number of stickers still missing.
+/// * `stickers_per_pack` - The number of stickers in a pack.
+/// * `pack_price` - The price of a pack.
+#[derive(Debug)]
+pub struct CompletionEstimate {
+    pub total: usize,
+    pub missing: usize,
+    pub stickers_per_pack: u32,
+    pub pack_price: f64,
+}
+
+impl CompletionEstimate {
+    /// Estimates the cost of finishing the collection only by buying packs.
+    ///
+    /// # Returns
+    ///
+    /// * `Cost` - The expected packs and money.
+    pub fn buying(&self) -> Cost {
+        self.buying_until(self.missing, 0)
+    }
+
+    /// Estimates the cost of finishing the collection after trading the current duplicates 1:1 for missing stickers.
+    ///
+    /// # Arguments
+    ///
+    /// * `duplicates` - The number of duplicates available for trading.
+    ///
+    /// # Returns
+    ///
+    /// * `Cost` - The expected packs and money.
+    pub fn trading(&self, duplicates: usize) -> Cost {
+        self.buying_until(self.missing.saturating_sub(duplicates), 0)
+    }
+
+    /// Estimates the cost of buying packs until only the last stickers are missing, and ordering those from the publisher.
+    ///
+    /// # Arguments
+    ///
+    /// * `ordered` - The number of missing stickers to order.
+    /// * `sticker_price` - The price of an ordered sticker.
+    ///
+    /// # Returns
+    ///
+    /// * `Cost` - The expected packs and money, including the ordered stickers.
+    pub fn ordering(&self, ordered: usize, sticker_price: f64) -> Cost {
+        let ordered = ordered.min(self.missing);
+        let cost = self.buying_until(self.missing, ordered);
+        Cost {
+            packs: cost.packs,
+            money: cost.money + ordered as f64 * sticker_price,
+        }
+    }
+
+    /// Estimates the cost of buying packs until only `left` of the `missing` stickers are still missing.
+    fn buying_until(&self, missing: usize, left: usize) -> Cost {
+        let stickers = self.total as f64 * (harmonic(missing) - harmonic(left));
+        let packs = if self.stickers_per_pack == 0 { 0.0 } else { stickers / self.stickers_per_pack as f64 };
+        Cost {
+            packs,
+            money: packs * self.pack_price,
+        }
+    }
+}
+
+/// Computes the `n`-th harmonic number, `1 + 1/2 + ... + 1/n`.
+fn harmonic(n: usize) -> f64 {
+    (1..=n).map(|i| 1.0 / i as f64).sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn estimate(total: usize, missing: usize) -> CompletionEstimate {
+        CompletionEstimate { total, missing, stickers_per_pack: 5, pack_price: 2.0 }
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+    }
+
+    #[test]
+    fn buying_follows_the_coupon_collector() {
+        // Missing 3 of 10: 10 * (1 + 1/2 + 1/3) = 18.33 stickers.
+        let cost = estimate(10, 3).buying();
+
+        assert_close(cost.packs, 110.0 / 30.0);
+        assert_close(cost.money, 220.0 / 30.0);
+    }
+
+    #[test]
+    fn complete_collection_costs_nothing() {
+        assert_eq!(estimate(10, 0).buying(), Cost { packs: 0.0, money: 0.0 });
+        assert_eq!(estimate(10, 3).trading(5), Cost { packs: 0.0, money: 0.0 });
+    }
+
+    #[test]
+    fn trading_and_ordering_lower_the_cost() {
+        let estimate = estimate(10, 3);
+
+        assert_close(estimate.trading(2).packs, 2.0);
+        // Buying until 1 is missing takes 10 * (1/2 + 1/3) stickers, and the last one costs 3.
+        let cost = estimate.ordering(1, 3.0);
+        assert_close(cost.packs, 50.0 / 30.0);
+        assert_close(cost.money, 100.0 / 30.0 + 3.0);
+        assert_close(estimate.ordering(10, 3.0).money, 9.0);
+    }
+}
@@ -0,0 +1,239 @@
+use crate::storage::{Snapshot, Storage};
+use crate::error::StickereError;
+
+/// The maximum number of commands that can be undone.
+const MAX_UNDO: usize = 100;
+
+/// A change made to the collections, recorded so it can be undone and redone.
+#[derive(Debug)]
+pub enum Command {
+    /// The collected status of a card was set to `collected`.
+    SetCollected { card_id: u32, collected: bool },
+    /// The duplicates of a card were changed from `before` to `after`.
+    SetDuplicates { card_id: u32, before: u32, after: u32 },
+    /// A card was added. While undone, `snapshot` holds the removed card so it can be added back.
+    AddCard { card_id: u32, snapshot: Option<Snapshot> },
+    /// A card was deleted. While not undone, `snapshot` holds the removed card so it can be added back.
+    DeleteCard { card_id: u32, snapshot: Option<Snapshot> },
+    /// The label of a card was changed from `before` to `after`.
+    RelabelCard { card_id: u32, before: String, after: String },
+    /// A collection was moved to the trash.
+    DeleteCollection { collection_id: u32 },
+}
+
+impl Command {
+    /// Reverts the change in the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn undo(&mut self, db_handler: &mut dyn Storage) -> Result<(), StickereError> {
+        match self {
+            Command::SetCollected { card_id, collected } => db_handler.update_card(*card_id, !*collected),
+            Command::SetDuplicates { card_id, before, .. } => db_handler.set_card_duplicates(*card_id, *before),
+            Command::AddCard { card_id, snapshot } => remove_card(db_handler, *card_id, snapshot),
+            Command::DeleteCard { snapshot, .. } => restore_card(db_handler, snapshot),
+            Command::RelabelCard { card_id, before, .. } => db_handler.update_card_label(*card_id, before),
+            Command::DeleteCollection { collection_id } => db_handler.restore_collection(*collection_id),
+        }
+    }
+
+    /// Applies the change in the database again, after it was undone.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn redo(&mut self, db_handler: &mut dyn Storage) -> Result<(), StickereError> {
+        match self {
+            Command::SetCollected { card_id, collected } => db_handler.update_card(*card_id, *collected),
+            Command::SetDuplicates { card_id, after, .. } => db_handler.set_card_duplicates(*card_id, *after),
+            Command::AddCard { snapshot, .. } => restore_card(db_handler, snapshot),
+            Command::DeleteCard { card_id, snapshot } => remove_card(db_handler, *card_id, snapshot),
+            Command::RelabelCard { card_id, after, .. } => db_handler.update_card_label(*card_id, after),
+            Command::DeleteCollection { collection_id } => db_handler.delete_collection(*collection_id),
+        }
+    }
+}
+
+/// Deletes a card, keeping a copy of it so it can be restored.
+fn remove_card(db_handler: &mut dyn Storage, card_id: u32, snapshot: &mut Option<Snapshot>) -> Result<(), StickereError> {
+    *snapshot = Some(db_handler.snapshot_card(card_id)?);
+    db_handler.delete_card(card_id)
+}
+
+/// Restores a card removed by `remove_card`.
+fn restore_card(db_handler: &mut dyn Storage, snapshot: &mut Option<Snapshot>) -> Result<(), StickereError> {
+    match snapshot.take() {
+        Some(card) => db_handler.restore(&card),
+        None => Ok(()),
+    }
+}
+
+/// The undo and redo stacks of the changes made to the collections.
+#[derive(Debug, Default)]
+pub struct History {
+    undo_stack: Vec<Command>,
+    redo_stack: Vec<Command>,
+}
+
+impl History {
+    /// Creates a new, empty `History` instance.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new instance of `History`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records a change that was just made. Changes undone before can no longer be redone.
+    ///
+    /// # Arguments
+    ///
+    /// * `command` - The `Command` describing the change.
+    pub fn push(&mut self, command: Command) {
+        self.redo_stack.clear();
+        self.undo_stack.push(command);
+        if self.undo_stack.len() > MAX_UNDO {
+            self.undo_stack.remove(0);
+        }
+    }
+
+    /// Forgets all the recorded changes, after the collections were changed in a way the history does not record,
+    /// such as resizing a collection, so the recorded changes can no longer be undone safely.
+    pub fn clear(&mut self) {
+        self.undo_stack.clear();
+        self.redo_stack.clear();
+    }
+
+    /// Undoes the most recent change. A change that cannot be undone is dropped, so the earlier changes
+    /// can still be undone.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, StickereError>` - Whether there was a change to undo.
+    pub fn undo(&mut self, db_handler: &mut dyn Storage) -> Result<bool, StickereError> {
+        let Some(mut command) = self.undo_stack.pop() else {
+            return Ok(false);
+        };
+
+        command.undo(db_handler)?;
+        self.redo_stack.push(command);
+        Ok(true)
+    }
+
+    /// Redoes the most recently undone change. A change that cannot be redone is dropped, so the later changes
+    /// can still be redone.
+    ///
+    /// # Arguments
+    ///
+    /// * `db_handler` - A mutable reference to the `Storage` for database operations.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, StickereError>` - Whether there was a change to redo.
+    pub fn redo(&mut self, db_handler: &mut dyn Storage) -> Result<bool, StickereError> {
+        let Some(mut command) = self.redo_stack.pop() else {
+            return Ok(false);
+        };
+
+        command.redo(db_handler)?;
+        self.undo_stack.push(command);
+        Ok(true)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db::DbHandler;
+
+    fn collected(db_handler: &dyn Storage, collection_id: u32) -> Vec<(String, bool, u32)> {
+        db_handler.get_cards_from_collection(collection_id).unwrap().into_iter()
+            .map(|card| (card.label, card.checked, card.duplicates))
+            .collect()
+    }
+
+    #[test]
+    fn card_changes_are_undone_and_redone() {
+        let mut db_handler = DbHandler::new(":memory:").unwrap();
+        let collection_id = db_handler.add_collection("Euro 2024", 2).unwrap();
+        let card_id = db_handler.get_cards_from_collection(collection_id).unwrap()[0].id;
+        let mut history = History::new();
+
+        db_handler.update_card(card_id, true).unwrap();
+        history.push(Command::SetCollected { card_id, collected: true });
+        db_handler.set_card_duplicates(card_id, 2).unwrap();
+        history.push(Command::SetDuplicates { card_id, before: 0, after: 2 });
+        let added_id = db_handler.add_card("30", collection_id).unwrap();
+        history.push(Command::AddCard { card_id: added_id, snapshot: None });
+        db_handler.update_card_label(added_id, "3").unwrap();
+        history.push(Command::RelabelCard { card_id: added_id, before: "30".to_string(), after: "3".to_string() });
+        let snapshot = Some(db_handler.snapshot_card(card_id).unwrap());
+        db_handler.delete_card(card_id).unwrap();
+        history.push(Command::DeleteCard { card_id, snapshot });
+        let done = collected(&db_handler, collection_id);
+        assert_eq!(done, [("2".to_string(), false, 0), ("3".to_string(), false, 0)]);
+
+        while history.undo(&mut db_handler).unwrap() {}
+        assert_eq!(collected(&db_handler, collection_id),
+                   [("1".to_string(), false, 0), ("2".to_string(), false, 0)]);
+
+        while history.redo(&mut db_handler).unwrap() {}
+        assert_eq!(collected(&db_handler, collection_id), done);
+    }
+
+    #[test]
+    fn deleted_collection_is_restored_with_its_history() {
+        let mut db_handler = DbHandler::new(":memory:").unwrap();
+        let collection_id = db_handler.add_collection("Euro 2024", 3).unwrap();
+        db_handler.add_section(collection_id, "Stadioane").unwrap();
+        db_handler.open_pack(collection_id, &["1".to_string(), "1".to_string()]).unwrap();
+        let mut history = History::new();
+
+        db_handler.delete_collection(collection_id).unwrap();
+        history.push(Command::DeleteCollection { collection_id });
+        assert!(db_handler.get_collections().unwrap().is_empty());
+
+        assert!(history.undo(&mut db_handler).unwrap());
+        assert_eq!(db_handler.get_collection_name(collection_id).unwrap(), "Euro 2024");
+        assert_eq!(collected(&db_handler, collection_id)[0], ("1".to_string(), true, 1));
+        assert_eq!(db_handler.get_sections_from_collection(collection_id).unwrap().len(), 1);
+        assert_eq!(db_handler.get_packs_from_collection(collection_id).unwrap()[0].summary.duplicates, ["1"]);
+
+        assert!(history.redo(&mut db_handler).unwrap());
+        assert!(db_handler.get_collections().unwrap().is_empty());
+        assert!(!history.redo(&mut db_handler).unwrap());
+    }
+
+    #[test]
+    fn change_that_cannot_be_undone_is_dropped() {
+        let mut db_handler = DbHandler::new(":memory:").unwrap();
+        let collection_id = db_handler.add_collection("Euro 2024", 1).unwrap();
+        let card_id = db_handler.get_cards_from_collection(collection_id).unwrap()[0].id;
+        let mut history = History::new();
+
+        db_handler.update_card(card_id, true).unwrap();
+        history.push(Command::SetCollected { card_id, collected: true });
+        let added_id = db_handler.add_card("2", collection_id).unwrap();
+        history.push(Command::AddCard { card_id: added_id, snapshot: None });
+        db_handler.resize_collection(collection_id, 1).unwrap();
+
+        assert!(history.undo(&mut db_handler).is_err());
+        assert!(history.undo(&mut db_handler).unwrap());
+        assert_eq!(collected(&db_handler, collection_id), [("1".to_string(), false, 0)]);
+        assert!(!history.undo(&mut db_handler).unwrap());
+    }
+}
@@ -0,0 +1,148 @@
+use std::cmp::Ordering;
+
+/// Compares two card labels in natural order, so that numeric parts are compared by value.
+/// This sorts `"2"` before `"10"` and `"FWC 9"` before `"FWC 10"`, while labels with
+/// different prefixes are grouped alphabetically by prefix.
+///
+/// # Arguments
+///
+/// * `a` - The first label.
+/// * `b` - The second label.
+///
+/// # Returns
+///
+/// * `Ordering` - The natural ordering of `a` relative to `b`.
+pub fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let mut a_chunks = chunks(a);
+    let mut b_chunks = chunks(b);
+
+    loop {
+        let ordering = match (a_chunks.next(), b_chunks.next()) {
+            (None, None) => break,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(a_chunk), Some(b_chunk)) => compare_chunks(a_chunk, b_chunk),
+        };
+
+        if ordering != Ordering::Equal {
+            return ordering;
+        }
+    }
+
+    // Labels such as "01" and "1" are equal by value, fall back to plain comparison
+    // so that the ordering stays total.
+    a.cmp(b)
+}
+
+/// Infers the label of the next card to add to a collection.
+/// The number at the end of the most recently added label is incremented, among all labels
+/// sharing its prefix, keeping the prefix and any zero padding, e.g. `"FWC 5"` becomes `"FWC 6"`
+/// and `"07"` becomes `"08"`.
+///
+/// # Arguments
+///
+/// * `labels` - The labels of the collection, in the order the cards were added.
+///
+/// # Returns
+///
+/// * `String` - The label of the next card.
+pub fn next_label(labels: &[String]) -> String {
+    let Some(last) = labels.last() else {
+        return "1".to_string();
+    };
+    let (prefix, _) = split_number(last);
+
+    let max_number = labels.iter()
+        .map(|label| split_number(label))
+        .filter(|(label_prefix, number)| *label_prefix == prefix && !number.is_empty())
+        .max_by(|(_, a), (_, b)| natural_cmp(a, b))
+        .map(|(_, number)| number);
+
+    match max_number {
+        Some(number) => {
+            let next = number.parse::<u64>().map_or(1, |value| value + 1);
+            format!("{prefix}{next:0width$}", width = number.len())
+        }
+        None => format!("{prefix}1"),
+    }
+}
+
+/// Splits a label into its prefix and the run of digits at its end.
+///
+/// # Arguments
+///
+/// * `label` - The label to split.
+///
+/// # Returns
+///
+/// * `(&str, &str)` - The prefix and the trailing number, which is empty if the label does not end in a digit.
+fn split_number(label: &str) -> (&str, &str) {
+    let split_at = label.trim_end_matches(|c: char| c.is_ascii_digit()).len();
+    label.split_at(split_at)
+}
+
+/// Splits a label into alternating runs of digits and non-digits.
+fn chunks(label: &str) -> impl Iterator<Item=&str> {
+    let mut rest = label;
+    std::iter::from_fn(move || {
+        let first = rest.chars().next()?;
+        let is_digit = first.is_ascii_digit();
+        let end = rest.find(|c: char| c.is_ascii_digit() != is_digit).unwrap_or(rest.len());
+        let (chunk, remaining) = rest.split_at(end);
+        rest = remaining;
+        Some(chunk)
+    })
+}
+
+/// Compares two chunks of a label, by value when both are numeric and case-insensitively otherwise.
+fn compare_chunks(a: &str, b: &str) -> Ordering {
+    let a_numeric = a.starts_with(|c: char| c.is_ascii_digit());
+    let b_numeric = b.starts_with(|c: char| c.is_ascii_digit());
+
+    match (a_numeric, b_numeric) {
+        (true, true) => {
+            let a = a.trim_start_matches('0');
+            let b = b.trim_start_matches('0');
+            a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+        }
+        (true, false) => Ordering::Less,
+        (false, true) => Ordering::Greater,
+        (false, false) => a.to_lowercase().cmp(&b.to_lowercase()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn labels(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|label| label.to_string()).collect()
+    }
+
+    #[test]
+    fn labels_are_sorted_naturally() {
+        let mut sorted = labels(&["10", "FWC 10", "2", "BRA 17", "00", "FWC 9", "1", "bra 2"]);
+        sorted.sort_by(|a, b| natural_cmp(a, b));
+
+        assert_eq!(sorted, ["00", "1", "2", "10", "bra 2", "BRA 17", "FWC 9", "FWC 10"]);
+    }
+
+    #[test]
+    fn next_label_of_empty_collection_is_one() {
+        assert_eq!(next_label(&[]), "1");
+    }
+
+    #[test]
+    fn next_label_keeps_prefix_and_padding() {
+        assert_eq!(next_label(&labels(&["1", "2", "3"])), "4");
+        assert_eq!(next_label(&labels(&["FWC 4", "FWC 5"])), "FWC 6");
+        assert_eq!(next_label(&labels(&["00", "07"])), "08");
+        assert_eq!(next_label(&labels(&["ARG12"])), "ARG13");
+    }
+
+    #[test]
+    fn next_label_follows_the_prefix_of_the_last_added_card() {
+        assert_eq!(next_label(&labels(&["1", "2", "BRA 20", "ARG 3", "ARG 1"])), "ARG 4");
+        assert_eq!(next_label(&labels(&["1", "2", "Logo"])), "Logo1");
+    }
+}
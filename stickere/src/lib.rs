@@ -0,0 +1,21 @@
+//! The data model and storage of the sticker collection manager, shared by the graphical application
+//! and the command-line interface.
+pub mod db;
+pub mod storage;
+pub mod memory_storage;
+pub mod profile;
+pub mod backup;
+pub mod card;
+pub mod card_details;
+pub mod collection;
+pub mod collection_stats;
+pub mod error;
+pub mod migrations;
+pub mod label;
+pub mod section;
+pub mod checklist;
+pub mod trade_list;
+pub mod trade;
+pub mod history;
+pub mod pack;
+pub mod estimate;
@@ -0,0 +1,683 @@
+use rusqlite::Connection;
+
+use crate::card::Card;
+use crate::card_details::CardDetails;
+use crate::checklist::ChecklistCard;
+use crate::collection::{Collection, DeletedCollection};
+use crate::error::StickereError;
+use crate::label;
+use crate::pack::{Pack, PackSummary};
+use crate::section::Section;
+use crate::storage::{Snapshot, Storage};
+use crate::trade::Trade;
+
+/// A collection as kept by `MemoryStorage`.
+///
+/// # Fields
+/// * `id` - A `u32` representing the unique identifier of the collection.
+/// * `name` - A `String` representing the name of the collection.
+/// * `deleted_at` - The date and time the collection was moved to the trash, if it is in the trash.
+#[derive(Debug, Clone)]
+struct StoredCollection {
+    id: u32,
+    name: String,
+    deleted_at: Option<String>,
+}
+
+/// Everything kept by `MemoryStorage`. Cards, sections, trades and packs are each stored
+/// with the identifier of their collection, in the order of their identifiers.
+#[derive(Debug, Clone, Default)]
+struct Data {
+    collections: Vec<StoredCollection>,
+    cards: Vec<(u32, Card)>,
+    sections: Vec<(u32, Section)>,
+    trades: Vec<(u32, Trade)>,
+    packs: Vec<(u32, Pack)>,
+    last_ids: LastIds,
+}
+
+/// The largest identifier given so far to a row of each table, kept like the `sqlite_sequence` table
+/// of SQLite, so that the identifier of a removed row is never given to another row.
+#[derive(Debug, Clone, Default)]
+struct LastIds {
+    collections: u32,
+    cards: u32,
+    sections: u32,
+    trades: u32,
+    packs: u32,
+}
+
+/// A storage that keeps the collections in memory, behaving like the SQLite database of `DbHandler`.
+/// It is meant for tests, and everything stored is lost when it is dropped.
+/// Dates are recorded in local time, as `YYYY-MM-DD HH:MM:SS`.
+#[derive(Debug, Default)]
+pub struct MemoryStorage {
+    data: Data,
+}
+
+impl MemoryStorage {
+    /// Creates a new, empty `MemoryStorage` instance.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new instance of `MemoryStorage`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Applies a change to a copy of the stored data, which replaces the data only if the change succeeds,
+    /// so that a failed change leaves everything as it was.
+    ///
+    /// # Arguments
+    ///
+    /// * `change` - The change to apply.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<T, StickereError>` - The result of the change.
+    fn transaction<T>(&mut self, change: impl FnOnce(&mut Data) -> Result<T, StickereError>) -> Result<T, StickereError> {
+        let mut data = self.data.clone();
+        let result = change(&mut data)?;
+        self.data = data;
+        Ok(result)
+    }
+}
+
+impl Data {
+    /// Looks up a card by its identifier.
+    fn card_mut(&mut self, card_id: u32) -> Option<&mut Card> {
+        self.cards.iter_mut().map(|(_, card)| card).find(|card| card.id == card_id)
+    }
+
+    /// Looks up a card of a collection by its label.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<&mut Card, StickereError>` - The card, or `CardNotFound` if there is none.
+    fn find_card(&mut self, collection_id: u32, label: &str) -> Result<&mut Card, StickereError> {
+        self.cards.iter_mut()
+            .find(|(id, card)| *id == collection_id && card.label == label)
+            .map(|(_, card)| card)
+            .ok_or_else(|| StickereError::CardNotFound(label.to_string()))
+    }
+
+    /// Adds a received copy of a card to the collection: the card is marked as collected,
+    /// or gains a duplicate if it was already collected.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<bool, StickereError>` - `true` if the card was not collected before.
+    fn receive_card(&mut self, collection_id: u32, label: &str) -> Result<bool, StickereError> {
+        let card = self.find_card(collection_id, label)?;
+        let new = !card.checked;
+        if new {
+            card.checked = true;
+        } else {
+            card.duplicates += 1;
+        }
+        Ok(new)
+    }
+
+    /// Returns the labels of the cards of a collection, in the order they were added.
+    fn labels(&self, collection_id: u32) -> Vec<String> {
+        self.cards.iter()
+            .filter(|(id, _)| *id == collection_id)
+            .map(|(_, card)| card.label.clone())
+            .collect()
+    }
+
+    /// Checks that a label is not used by another card of a collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - `DuplicateLabel` if another card already has the label.
+    fn check_label(&self, collection_id: u32, label: &str, card_id: Option<u32>) -> Result<(), StickereError> {
+        let taken = self.cards.iter()
+            .any(|(id, card)| *id == collection_id && card.label == label && Some(card.id) != card_id);
+        if taken {
+            return Err(StickereError::DuplicateLabel(label.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Adds a card with the next free identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u32, StickereError>` - The identifier of the new card, or `DuplicateLabel` if the label is taken.
+    fn insert_card(&mut self, collection_id: u32, label: &str, details: CardDetails) -> Result<u32, StickereError> {
+        self.check_label(collection_id, label, None)?;
+        let card_id = next_id(&mut self.last_ids.cards);
+        self.cards.push((collection_id, Card::new(label.to_string(), card_id, false, 0, None, details)));
+        Ok(card_id)
+    }
+
+    /// Adds a section with the next free identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - The identifier of the new section.
+    fn insert_section(&mut self, collection_id: u32, name: &str) -> u32 {
+        let section_id = next_id(&mut self.last_ids.sections);
+        self.sections.push((collection_id, Section::new(section_id, name.to_string())));
+        section_id
+    }
+
+    /// Checks that a collection exists, in the trash or not.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - `CollectionNotFound` if there is no such collection.
+    fn check_collection(&self, collection_id: u32) -> Result<(), StickereError> {
+        if self.collections.iter().any(|collection| collection.id == collection_id) {
+            Ok(())
+        } else {
+            Err(StickereError::CollectionNotFound(collection_id.to_string()))
+        }
+    }
+}
+
+impl Storage for MemoryStorage {
+    fn get_collections(&self) -> Result<Vec<Collection>, StickereError> {
+        let data = &self.data;
+
+        Ok(data.collections.iter()
+            .filter(|collection| collection.deleted_at.is_none())
+            .map(|collection| {
+                let cards: Vec<&Card> = data.cards.iter()
+                    .filter(|(id, _)| *id == collection.id)
+                    .map(|(_, card)| card)
+                    .collect();
+                let collected = cards.iter().filter(|card| card.checked).count();
+                Collection::new(collection.id, collection.name.clone(), collected, cards.len())
+            })
+            .collect())
+    }
+
+    fn get_cards_from_collection(&self, id: u32) -> Result<Vec<Card>, StickereError> {
+        let mut cards: Vec<Card> = self.data.cards.iter()
+            .filter(|(collection_id, _)| *collection_id == id)
+            .map(|(_, card)| card.clone())
+            .collect();
+        cards.sort_by(|a, b| label::natural_cmp(&a.label, &b.label));
+        Ok(cards)
+    }
+
+    fn update_card(&mut self, id: u32, status: bool) -> Result<(), StickereError> {
+        if let Some(card) = self.data.card_mut(id) {
+            card.checked = status;
+        }
+        Ok(())
+    }
+
+    fn add_collection(&mut self, name: &str, size: u32) -> Result<u32, StickereError> {
+        let data = &mut self.data;
+        let collection_id = next_id(&mut data.last_ids.collections);
+        data.collections.push(StoredCollection { id: collection_id, name: name.to_string(), deleted_at: None });
+
+        for label in 1..=size {
+            data.insert_card(collection_id, &label.to_string(), CardDetails::default())?;
+        }
+        Ok(collection_id)
+    }
+
+    fn rename_collection(&mut self, collection_id: u32, name: &str) -> Result<(), StickereError> {
+        let data = &mut self.data;
+        if let Some(collection) = data.collections.iter_mut().find(|collection| collection.id == collection_id) {
+            collection.name = name.to_string();
+        }
+        Ok(())
+    }
+
+    fn resize_collection(&mut self, collection_id: u32, size: u32) -> Result<(), StickereError> {
+        let cards = self.get_cards_from_collection(collection_id)?;
+        let size = size as usize;
+
+        self.transaction(|data| {
+            if size > cards.len() {
+                let mut labels = data.labels(collection_id);
+                for _ in cards.len()..size {
+                    let label = label::next_label(&labels);
+                    data.insert_card(collection_id, &label, CardDetails::default())?;
+                    labels.push(label);
+                }
+            } else {
+                for card in &cards[size..] {
+                    if card.checked || card.duplicates > 0 || !card.details.is_empty() {
+                        return Err(StickereError::CardNotEmpty(card.label.clone()));
+                    }
+                    data.cards.retain(|(_, stored)| stored.id != card.id);
+                }
+            }
+            Ok(())
+        })
+    }
+
+    fn delete_collection(&mut self, collection_id: u32) -> Result<(), StickereError> {
+        let data = &mut self.data;
+        if let Some(collection) = data.collections.iter_mut().find(|collection| collection.id == collection_id) {
+            collection.deleted_at = Some(now()?);
+        }
+        Ok(())
+    }
+
+    fn restore_collection(&mut self, collection_id: u32) -> Result<(), StickereError> {
+        let data = &mut self.data;
+        if let Some(collection) = data.collections.iter_mut().find(|collection| collection.id == collection_id) {
+            collection.deleted_at = None;
+        }
+        Ok(())
+    }
+
+    fn purge_collection(&mut self, collection_id: u32) -> Result<(), StickereError> {
+        let data = &mut self.data;
+        let in_trash = data.collections.iter()
+            .any(|collection| collection.id == collection_id && collection.deleted_at.is_some());
+
+        if in_trash {
+            data.collections.retain(|collection| collection.id != collection_id);
+            data.cards.retain(|(id, _)| *id != collection_id);
+            data.sections.retain(|(id, _)| *id != collection_id);
+            data.trades.retain(|(id, _)| *id != collection_id);
+            data.packs.retain(|(id, _)| *id != collection_id);
+        }
+        Ok(())
+    }
+
+    fn get_deleted_collections(&self) -> Result<Vec<DeletedCollection>, StickereError> {
+        let mut deleted: Vec<DeletedCollection> = self.data.collections.iter()
+            .filter_map(|collection| Some(DeletedCollection {
+                id: collection.id,
+                name: collection.name.clone(),
+                deleted_at: collection.deleted_at.clone()?,
+            }))
+            .collect();
+        deleted.sort_by(|a, b| (&b.deleted_at, b.id).cmp(&(&a.deleted_at, a.id)));
+        Ok(deleted)
+    }
+
+    fn get_collection_name(&self, collection_id: u32) -> Result<String, StickereError> {
+        self.data.collections.iter()
+            .find(|collection| collection.id == collection_id)
+            .map(|collection| collection.name.clone())
+            .ok_or_else(|| StickereError::CollectionNotFound(collection_id.to_string()))
+    }
+
+    fn get_next_label_for_collection(&self, collection_id: u32) -> Result<String, StickereError> {
+        Ok(label::next_label(&self.data.labels(collection_id)))
+    }
+
+    fn add_card(&mut self, card_number: &str, collection_id: u32) -> Result<u32, StickereError> {
+        let data = &mut self.data;
+        data.check_collection(collection_id)?;
+        data.insert_card(collection_id, card_number, CardDetails::default())
+    }
+
+    fn update_card_duplicates(&mut self, card: &mut Card) -> Result<(), StickereError> {
+        self.set_card_duplicates(card.id, card.duplicates)
+    }
+
+    fn set_card_duplicates(&mut self, card_id: u32, duplicates: u32) -> Result<(), StickereError> {
+        if let Some(card) = self.data.card_mut(card_id) {
+            card.duplicates = duplicates;
+        }
+        Ok(())
+    }
+
+    fn update_card_label(&mut self, card_id: u32, label: &str) -> Result<(), StickereError> {
+        let data = &mut self.data;
+        let Some(&(collection_id, _)) = data.cards.iter().find(|(_, card)| card.id == card_id) else {
+            return Ok(());
+        };
+
+        data.check_label(collection_id, label, Some(card_id))?;
+        if let Some(card) = data.card_mut(card_id) {
+            card.label = label.to_string();
+        }
+        Ok(())
+    }
+
+    fn delete_card(&mut self, card_id: u32) -> Result<(), StickereError> {
+        self.data.cards.retain(|(_, card)| card.id != card_id);
+        Ok(())
+    }
+
+    fn snapshot_card(&self, card_id: u32) -> Result<Snapshot, StickereError> {
+        self.data.cards.iter()
+            .find(|(_, card)| card.id == card_id)
+            .map(|(collection_id, card)| Snapshot { collection_id: *collection_id, card: card.clone() })
+            .ok_or_else(|| StickereError::CardNotFound(card_id.to_string()))
+    }
+
+    fn restore(&mut self, snapshot: &Snapshot) -> Result<(), StickereError> {
+        let data = &mut self.data;
+        data.check_collection(snapshot.collection_id)?;
+        data.check_label(snapshot.collection_id, &snapshot.card.label, None)?;
+
+        let index = data.cards.partition_point(|(_, card)| card.id < snapshot.card.id);
+        data.cards.insert(index, (snapshot.collection_id, snapshot.card.clone()));
+        Ok(())
+    }
+
+    fn update_card_details(&mut self, card: &Card) -> Result<(), StickereError> {
+        if let Some(stored) = self.data.card_mut(card.id) {
+            stored.details = card.details.clone();
+        }
+        Ok(())
+    }
+
+    fn get_sections_from_collection(&self, collection_id: u32) -> Result<Vec<Section>, StickereError> {
+        Ok(self.data.sections.iter()
+            .filter(|(id, _)| *id == collection_id)
+            .map(|(_, section)| section.clone())
+            .collect())
+    }
+
+    fn add_section(&mut self, collection_id: u32, name: &str) -> Result<u32, StickereError> {
+        let data = &mut self.data;
+        data.check_collection(collection_id)?;
+        Ok(data.insert_section(collection_id, name))
+    }
+
+    fn assign_cards_to_section(&mut self, collection_id: u32, section_id: u32,
+                               first_label: &str, last_label: &str) -> Result<usize, StickereError> {
+        let mut moved = 0;
+
+        for (_, card) in self.data.cards.iter_mut().filter(|(id, card)| {
+            *id == collection_id &&
+                label::natural_cmp(&card.label, first_label).is_ge() &&
+                label::natural_cmp(&card.label, last_label).is_le()
+        }) {
+            card.section_id = Some(section_id);
+            moved += 1;
+        }
+
+        Ok(moved)
+    }
+
+    fn import_collection(&mut self, name: &str, sections: &[String],
+                         cards: &[ChecklistCard]) -> Result<u32, StickereError> {
+        self.transaction(|data| {
+            let collection_id = next_id(&mut data.last_ids.collections);
+            data.collections.push(StoredCollection { id: collection_id, name: name.to_string(), deleted_at: None });
+            let mut section_ids: Vec<(&str, u32)> = vec![];
+
+            for section in sections.iter().map(String::as_str).chain(cards.iter().filter_map(|card| card.section.as_deref())) {
+                if !section_ids.iter().any(|(name, _)| *name == section) {
+                    section_ids.push((section, data.insert_section(collection_id, section)));
+                }
+            }
+
+            for card in cards {
+                let card_id = data.insert_card(collection_id, &card.label, card.details.clone())?;
+                if let Some(stored) = data.card_mut(card_id) {
+                    stored.checked = card.collected;
+                    stored.duplicates = card.duplicates;
+                    stored.section_id = section_ids.iter()
+                        .find(|(name, _)| Some(*name) == card.section.as_deref())
+                        .map(|(_, section_id)| *section_id);
+                }
+            }
+
+            Ok(collection_id)
+        })
+    }
+
+    fn set_collected(&mut self, collection_id: u32, labels: &[String], collected: bool) -> Result<(), StickereError> {
+        self.transaction(|data| {
+            for label in labels {
+                data.find_card(collection_id, label)?.checked = collected;
+            }
+            Ok(())
+        })
+    }
+
+    fn change_duplicates(&mut self, collection_id: u32, changes: &[(String, i64)]) -> Result<(), StickereError> {
+        self.transaction(|data| {
+            for (label, change) in changes {
+                let card = data.find_card(collection_id, label)?;
+                let duplicates = card.duplicates as i64 + change;
+                if duplicates < 0 {
+                    return Err(StickereError::NoDuplicateToRemove(label.clone()));
+                }
+                card.duplicates = duplicates as u32;
+            }
+            Ok(())
+        })
+    }
+
+    fn record_trade(&mut self, collection_id: u32, partner: &str, given: &[String],
+                    received: &[String]) -> Result<u32, StickereError> {
+        self.transaction(|data| {
+            data.check_collection(collection_id)?;
+
+            for label in given {
+                let card = data.find_card(collection_id, label)?;
+                if card.duplicates == 0 {
+                    return Err(StickereError::NoDuplicateToGive(label.clone()));
+                }
+                card.duplicates -= 1;
+            }
+
+            for label in received {
+                data.receive_card(collection_id, label)?;
+            }
+
+            let trade_id = next_id(&mut data.last_ids.trades);
+            data.trades.push((collection_id, Trade {
+                id: trade_id,
+                partner: partner.to_string(),
+                traded_at: now()?,
+                given: given.to_vec(),
+                received: received.to_vec(),
+            }));
+            Ok(trade_id)
+        })
+    }
+
+    fn open_pack(&mut self, collection_id: u32, labels: &[String]) -> Result<PackSummary, StickereError> {
+        self.transaction(|data| {
+            data.check_collection(collection_id)?;
+            let mut summary = PackSummary::default();
+
+            for label in labels {
+                if data.receive_card(collection_id, label)? {
+                    summary.new.push(label.clone());
+                } else {
+                    summary.duplicates.push(label.clone());
+                }
+            }
+
+            let pack_id = next_id(&mut data.last_ids.packs);
+            data.packs.push((collection_id, Pack { id: pack_id, opened_at: now()?, summary: summary.clone() }));
+            Ok(summary)
+        })
+    }
+
+    fn get_trades_from_collection(&self, collection_id: u32) -> Result<Vec<Trade>, StickereError> {
+        let mut trades: Vec<Trade> = self.data.trades.iter()
+            .filter(|(id, _)| *id == collection_id)
+            .map(|(_, trade)| trade.clone())
+            .collect();
+        trades.sort_by(|a, b| (&b.traded_at, b.id).cmp(&(&a.traded_at, a.id)));
+        Ok(trades)
+    }
+
+    fn get_packs_from_collection(&self, collection_id: u32) -> Result<Vec<Pack>, StickereError> {
+        let mut packs: Vec<Pack> = self.data.packs.iter()
+            .filter(|(id, _)| *id == collection_id)
+            .map(|(_, pack)| pack.clone())
+            .collect();
+        packs.sort_by(|a, b| (&a.opened_at, a.id).cmp(&(&b.opened_at, b.id)));
+        Ok(packs)
+    }
+}
+
+/// Picks the identifier of a new row the way SQLite does for an `AUTOINCREMENT` table,
+/// one past the largest identifier ever given, even if that row was removed since.
+///
+/// # Arguments
+///
+/// * `last_id` - A mutable reference to the largest identifier given so far, which becomes the new one.
+///
+/// # Returns
+///
+/// * `u32` - The identifier of the new row.
+fn next_id(last_id: &mut u32) -> u32 {
+    *last_id += 1;
+    *last_id
+}
+
+/// Formats the current local date and time as `YYYY-MM-DD HH:MM:SS`, asking SQLite for it
+/// so the dates are the same as the ones returned by `DbHandler`.
+///
+/// # Returns
+///
+/// * `Result<String, StickereError>` - The current date and time.
+fn now() -> Result<String, StickereError> {
+    let connection = Connection::open_in_memory()?;
+    Ok(connection.query_row("SELECT datetime('now', 'localtime')", [], |row| row.get(0))?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card_details::Rarity;
+    use crate::checklist::{self, ExportFormat};
+    use crate::db::DbHandler;
+
+    /// Describes everything held by a storage, apart from the dates it recorded.
+    fn contents(storage: &dyn Storage) -> Vec<String> {
+        let collections = storage.get_collections().unwrap();
+        let deleted = storage.get_deleted_collections().unwrap();
+        let mut contents = vec![format!("{collections:?}"),
+                                format!("{:?}", deleted.iter().map(|collection| collection.id).collect::<Vec<_>>())];
+
+        for id in collections.iter().map(|collection| collection.id).chain(deleted.iter().map(|collection| collection.id)) {
+            contents.push(format!("{:?}", storage.get_cards_from_collection(id).unwrap()));
+            contents.push(format!("{:?}", storage.get_sections_from_collection(id).unwrap()));
+            contents.push(format!("{:?}", storage.get_trades_from_collection(id).unwrap().iter()
+                .map(|trade| (trade.id, &trade.partner, &trade.given, &trade.received))
+                .collect::<Vec<_>>()));
+            contents.push(format!("{:?}", storage.get_packs_from_collection(id).unwrap().iter()
+                .map(|pack| (pack.id, &pack.summary))
+                .collect::<Vec<_>>()));
+            contents.push(storage.export_collection(id, ExportFormat::Json).unwrap());
+            contents.push(storage.get_next_label_for_collection(id).unwrap());
+        }
+
+        contents
+    }
+
+    /// Applies the same changes to an SQLite database and to a `MemoryStorage`,
+    /// checking that both give the same results and end up holding the same collections.
+    fn assert_same_as_sqlite(changes: impl Fn(&mut dyn Storage) -> Vec<Result<String, String>>) {
+        let mut db_handler = DbHandler::new(":memory:").unwrap();
+        let mut memory_storage = MemoryStorage::new();
+
+        assert_eq!(changes(&mut memory_storage), changes(&mut db_handler));
+        assert_eq!(contents(&memory_storage), contents(&db_handler));
+    }
+
+    /// Describes the outcome of a change.
+    fn outcome<T: std::fmt::Debug>(result: Result<T, StickereError>) -> Result<String, String> {
+        result.map(|value| format!("{value:?}")).map_err(|err| err.to_string())
+    }
+
+    fn labels(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|label| label.to_string()).collect()
+    }
+
+    #[test]
+    fn card_changes_behave_like_sqlite() {
+        assert_same_as_sqlite(|storage| {
+            let collection_id = storage.add_collection("Euro 2024", 3).unwrap();
+            let mut cards = storage.get_cards_from_collection(collection_id).unwrap();
+            let card_id = cards[0].id;
+            storage.update_card(card_id, true).unwrap();
+            cards[1].duplicates = 2;
+            storage.update_card_duplicates(&mut cards[1]).unwrap();
+            cards[2].details.name = "Hagi".to_string();
+            cards[2].details.rarity = Rarity::Legend;
+            storage.update_card_details(&cards[2]).unwrap();
+
+            let snapshot = storage.snapshot_card(card_id).unwrap();
+            storage.delete_card(card_id).unwrap();
+            let removed_id = storage.add_card("30", collection_id).unwrap();
+            storage.delete_card(removed_id).unwrap();
+
+            vec![
+                outcome(storage.snapshot_card(card_id)),
+                outcome(storage.add_card("FWC 1", collection_id)),
+                outcome(storage.add_card("2", collection_id)),
+                outcome(storage.update_card_label(card_id + 1, "3")),
+                outcome(storage.update_card_label(card_id + 1, "20")),
+                outcome(storage.set_card_duplicates(card_id + 1, 1)),
+                outcome(storage.restore(&snapshot)),
+                outcome(storage.add_card("1", collection_id)),
+            ]
+        });
+    }
+
+    #[test]
+    fn collection_changes_behave_like_sqlite() {
+        assert_same_as_sqlite(|storage| {
+            let kept_id = storage.add_collection("Euro 2024", 3).unwrap();
+            let deleted_id = storage.add_collection("Euro 2020", 2).unwrap();
+            let purged_id = storage.add_collection("Euro 2016", 2).unwrap();
+            storage.open_pack(purged_id, &labels(&["1", "2"])).unwrap();
+
+            vec![
+                outcome(storage.rename_collection(kept_id, "Euro 2028")),
+                outcome(storage.resize_collection(kept_id, 5)),
+                outcome(storage.set_collected(kept_id, &labels(&["5"]), true)),
+                outcome(storage.resize_collection(kept_id, 2)),
+                outcome(storage.set_collected(kept_id, &labels(&["5"]), false)),
+                outcome(storage.resize_collection(kept_id, 2)),
+                outcome(storage.purge_collection(kept_id)),
+                outcome(storage.delete_collection(deleted_id)),
+                outcome(storage.delete_collection(purged_id)),
+                outcome(storage.purge_collection(purged_id)),
+                outcome(storage.get_collection_name(deleted_id)),
+            ]
+        });
+    }
+
+    #[test]
+    fn bulk_changes_behave_like_sqlite() {
+        assert_same_as_sqlite(|storage| {
+            let collection_id = storage.add_collection("Euro 2024", 6).unwrap();
+
+            vec![
+                outcome(storage.set_collected(collection_id, &labels(&["1", "2", "99"]), true)),
+                outcome(storage.set_collected(collection_id, &labels(&["1", "2"]), true)),
+                outcome(storage.change_duplicates(collection_id, &[("1".to_string(), 2), ("3".to_string(), -1)])),
+                outcome(storage.change_duplicates(collection_id, &[("1".to_string(), 2), ("3".to_string(), 1)])),
+                outcome(storage.open_pack(collection_id, &labels(&["4", "1", "99"]))),
+                outcome(storage.open_pack(collection_id, &labels(&["4", "1", "4"]))),
+                outcome(storage.record_trade(collection_id, "Ana", &labels(&["2"]), &labels(&["5"]))),
+                outcome(storage.record_trade(collection_id, "Ana", &labels(&["1", "3"]), &labels(&["5", "6"]))),
+                outcome(storage.record_trade(collection_id, "Dan", &labels(&["4"]), &[])),
+            ]
+        });
+    }
+
+    #[test]
+    fn sections_and_imports_behave_like_sqlite() {
+        let checklist = checklist::parse_csv("label,name,section,collected,duplicates\n\
+                                              1,Stadion,Stadioane,true,0\n\
+                                              2,,Stadioane,false,0\n\
+                                              3,Hagi,Romania,true,2\n\
+                                              4,,,false,0\n").unwrap();
+
+        assert_same_as_sqlite(|storage| {
+            let collection_id = storage.add_collection("Euro 2024", 12).unwrap();
+            let section_id = storage.add_section(collection_id, "Stadioane").unwrap();
+
+            vec![
+                outcome(storage.assign_cards_to_section(collection_id, section_id, "2", "10")),
+                outcome(storage.import_collection("Euro 2020", &labels(&["Legende", "Stadioane"]), &checklist.cards)),
+            ]
+        });
+    }
+}
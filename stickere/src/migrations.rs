@@ -0,0 +1,346 @@
+use rusqlite::Connection;
+
+use crate::error::StickereError;
+
+/// The schema migrations, in order.
+/// The migration at index `i` upgrades a database from `user_version` `i` to `i + 1`,
+/// so new migrations must only ever be appended to the end of this list.
+const MIGRATIONS: &[&str] = &[
+    // 1: The initial schema. Databases created before versioning existed already have these
+    // tables and report version 0, which is why the statements must stay idempotent.
+    "CREATE TABLE IF NOT EXISTS collections (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        name TEXT NOT NULL,
+        size INTEGER
+    );
+
+    CREATE TABLE IF NOT EXISTS cards (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        collection_id INTEGER NOT NULL,
+        card_number INTEGER NOT NULL,
+        collected BOOLEAN NOT NULL,
+        duplicates INTEGER DEFAULT 0,
+        FOREIGN KEY (collection_id) REFERENCES collections(id)
+    );",
+    // 2: Rebuilds `cards` so deleting a collection cascades to its cards. Cards orphaned by
+    // collections that were deleted half way through in older versions are removed first,
+    // otherwise they would violate the now enforced foreign key.
+    "DELETE FROM cards WHERE collection_id NOT IN (SELECT id FROM collections);
+
+    CREATE TABLE cards_new (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        collection_id INTEGER NOT NULL,
+        card_number INTEGER NOT NULL,
+        collected BOOLEAN NOT NULL,
+        duplicates INTEGER DEFAULT 0,
+        FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE
+    );
+
+    INSERT INTO cards_new (id, collection_id, card_number, collected, duplicates)
+        SELECT id, collection_id, card_number, collected, duplicates FROM cards;
+
+    DROP TABLE cards;
+    ALTER TABLE cards_new RENAME TO cards;",
+    // 3: Stores card labels as text, so albums can use codes such as "00" or "FWC 5".
+    "CREATE TABLE cards_new (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        collection_id INTEGER NOT NULL,
+        card_number TEXT NOT NULL,
+        collected BOOLEAN NOT NULL,
+        duplicates INTEGER DEFAULT 0,
+        FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE
+    );
+
+    INSERT INTO cards_new (id, collection_id, card_number, collected, duplicates)
+        SELECT id, collection_id, CAST(card_number AS TEXT), collected, duplicates FROM cards;
+
+    DROP TABLE cards;
+    ALTER TABLE cards_new RENAME TO cards;",
+    // 4: Album sections grouping the cards of a collection. Cards without a section stay ungrouped.
+    "CREATE TABLE sections (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        collection_id INTEGER NOT NULL,
+        name TEXT NOT NULL,
+        FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE
+    );
+
+    ALTER TABLE cards ADD COLUMN section_id INTEGER REFERENCES sections(id) ON DELETE SET NULL;",
+    // 5: Optional descriptive details of a card.
+    "ALTER TABLE cards ADD COLUMN name TEXT;
+    ALTER TABLE cards ADD COLUMN category TEXT;
+    ALTER TABLE cards ADD COLUMN rarity TEXT NOT NULL DEFAULT 'common';
+    ALTER TABLE cards ADD COLUMN notes TEXT;",
+    // 6: History of the trades made with other collectors. Card labels are copied into the
+    // history, so it stays readable after cards are edited or removed.
+    "CREATE TABLE trades (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        collection_id INTEGER NOT NULL,
+        partner TEXT NOT NULL,
+        traded_at TEXT NOT NULL DEFAULT (datetime('now')),
+        FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE
+    );
+
+    CREATE TABLE trade_cards (
+        trade_id INTEGER NOT NULL,
+        card_number TEXT NOT NULL,
+        given BOOLEAN NOT NULL,
+        FOREIGN KEY (trade_id) REFERENCES trades(id) ON DELETE CASCADE
+    );",
+    // 7: History of the opened packs. Like trades, the stickers keep their labels and whether they
+    // were new at the time, so the statistics do not change when cards are edited later.
+    "CREATE TABLE packs (
+        id INTEGER PRIMARY KEY AUTOINCREMENT,
+        collection_id INTEGER NOT NULL,
+        opened_at TEXT NOT NULL DEFAULT (datetime('now')),
+        FOREIGN KEY (collection_id) REFERENCES collections(id) ON DELETE CASCADE
+    );
+
+    CREATE TABLE pack_cards (
+        pack_id INTEGER NOT NULL,
+        card_number TEXT NOT NULL,
+        new BOOLEAN NOT NULL,
+        FOREIGN KEY (pack_id) REFERENCES packs(id) ON DELETE CASCADE
+    );",
+    // 8: Deleted collections are moved to the trash first, marked with the time they were deleted.
+    "ALTER TABLE collections ADD COLUMN deleted_at TEXT;",
+    // 9: Keeps `collections.size` equal to the number of cards of the collection, however cards are
+    // added or removed. Migrations rebuilding `cards` drop these triggers and have to create them again.
+    "UPDATE collections SET size = (SELECT count(*) FROM cards WHERE cards.collection_id = collections.id);
+
+    CREATE TRIGGER cards_size_after_insert AFTER INSERT ON cards BEGIN
+        UPDATE collections SET size = size + 1 WHERE id = NEW.collection_id;
+    END;
+
+    CREATE TRIGGER cards_size_after_delete AFTER DELETE ON cards BEGIN
+        UPDATE collections SET size = size - 1 WHERE id = OLD.collection_id;
+    END;",
+    // 10: Card labels are unique within a collection. Cards sharing a label are merged into the oldest one
+    // first: every collected copy and every duplicate is a sticker owned, so the merged card is collected
+    // if any copy was, and keeps all the other owned stickers as duplicates. Missing details are taken
+    // from the merged copies.
+    "UPDATE cards SET
+        collected = (SELECT max(collected) FROM cards AS copy
+                     WHERE copy.collection_id = cards.collection_id AND copy.card_number = cards.card_number),
+        duplicates = (SELECT max(sum(collected) + sum(coalesce(duplicates, 0)) - 1, 0) FROM cards AS copy
+                      WHERE copy.collection_id = cards.collection_id AND copy.card_number = cards.card_number),
+        section_id = coalesce(section_id, (SELECT max(section_id) FROM cards AS copy
+                     WHERE copy.collection_id = cards.collection_id AND copy.card_number = cards.card_number)),
+        name = coalesce(name, (SELECT max(name) FROM cards AS copy
+               WHERE copy.collection_id = cards.collection_id AND copy.card_number = cards.card_number)),
+        category = coalesce(category, (SELECT max(category) FROM cards AS copy
+                   WHERE copy.collection_id = cards.collection_id AND copy.card_number = cards.card_number)),
+        notes = coalesce(notes, (SELECT max(notes) FROM cards AS copy
+                WHERE copy.collection_id = cards.collection_id AND copy.card_number = cards.card_number))
+    WHERE id IN (SELECT min(id) FROM cards GROUP BY collection_id, card_number HAVING count(*) > 1);
+
+    DELETE FROM cards WHERE id NOT IN (SELECT min(id) FROM cards GROUP BY collection_id, card_number);
+
+    CREATE UNIQUE INDEX cards_collection_label ON cards (collection_id, card_number);",
+];
+
+/// The schema version of a fully migrated database.
+pub const LATEST_VERSION: u32 = MIGRATIONS.len() as u32;
+
+/// Reads the schema version stored in the `user_version` pragma of the database.
+///
+/// # Arguments
+///
+/// * `conn` - A reference to the `Connection` object.
+///
+/// # Returns
+///
+/// * `Result<u32, StickereError>` - The schema version of the database.
+pub fn schema_version(conn: &Connection) -> Result<u32, StickereError> {
+    Ok(conn.pragma_query_value(None, "user_version", |row| row.get(0))?)
+}
+
+/// Upgrades the database to the latest schema version, one migration at a time.
+/// Every migration runs in its own transaction together with the version bump,
+/// so an interrupted upgrade resumes from the last completed step.
+///
+/// Foreign keys are switched off while migrating, since some migrations rebuild tables,
+/// and the caller is responsible for enabling them again afterwards.
+///
+/// # Arguments
+///
+/// * `conn` - A mutable reference to the `Connection` object.
+///
+/// # Returns
+///
+/// * `Result<(), StickereError>` - A result indicating success or failure.
+pub fn migrate(conn: &mut Connection) -> Result<(), StickereError> {
+    let version = schema_version(conn)?;
+    if version > LATEST_VERSION {
+        return Err(StickereError::UnsupportedSchemaVersion(version));
+    }
+
+    conn.pragma_update(None, "foreign_keys", false)?;
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(version as usize) {
+        let tx = conn.transaction()?;
+        tx.execute_batch(migration)?;
+        tx.pragma_update(None, "user_version", index as u32 + 1)?;
+        tx.commit()?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const V1_FIXTURE: &str = include_str!("../tests/fixtures/stick_v1.sql");
+
+    fn open_v1_fixture() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(V1_FIXTURE).unwrap();
+        conn
+    }
+
+    #[test]
+    fn fresh_database_is_migrated_to_latest() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        migrate(&mut conn).unwrap();
+
+        assert_eq!(schema_version(&conn).unwrap(), LATEST_VERSION);
+        let collections: u32 = conn
+            .query_row("SELECT count(*) FROM collections", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(collections, 0);
+    }
+
+    #[test]
+    fn v1_fixture_is_migrated_without_losing_data() {
+        let mut conn = open_v1_fixture();
+        assert_eq!(schema_version(&conn).unwrap(), 0);
+
+        migrate(&mut conn).unwrap();
+
+        assert_eq!(schema_version(&conn).unwrap(), LATEST_VERSION);
+        let names: Vec<String> = conn
+            .prepare("SELECT name FROM collections ORDER BY id").unwrap()
+            .query_map([], |row| row.get(0)).unwrap()
+            .collect::<Result<_, _>>().unwrap();
+        assert_eq!(names, ["Euro 2024", "Champions League"]);
+
+        let (cards, collected, duplicates): (u32, u32, u32) = conn
+            .query_row("SELECT count(*), sum(collected), sum(duplicates) FROM cards", [],
+                       |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))
+            .unwrap();
+        assert_eq!((cards, collected, duplicates), (5, 3, 3));
+    }
+
+    #[test]
+    fn card_numbers_are_converted_to_text_labels() {
+        let mut conn = open_v1_fixture();
+        migrate(&mut conn).unwrap();
+
+        let labels: Vec<(String, String)> = conn
+            .prepare("SELECT card_number, typeof(card_number) FROM cards WHERE collection_id = 1 ORDER BY id").unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?))).unwrap()
+            .collect::<Result<_, _>>().unwrap();
+        assert_eq!(labels, [("1".to_string(), "text".to_string()),
+                            ("2".to_string(), "text".to_string()),
+                            ("3".to_string(), "text".to_string())]);
+    }
+
+    #[test]
+    fn orphaned_cards_are_removed() {
+        let mut conn = open_v1_fixture();
+        let orphans = "SELECT count(*) FROM cards WHERE collection_id NOT IN (SELECT id FROM collections)";
+        let before: u32 = conn.query_row(orphans, [], |row| row.get(0)).unwrap();
+        assert_eq!(before, 1);
+
+        migrate(&mut conn).unwrap();
+
+        let after: u32 = conn.query_row(orphans, [], |row| row.get(0)).unwrap();
+        assert_eq!(after, 0);
+    }
+
+    #[test]
+    fn deleting_a_collection_cascades_to_its_cards() {
+        let mut conn = open_v1_fixture();
+        migrate(&mut conn).unwrap();
+        conn.pragma_update(None, "foreign_keys", true).unwrap();
+
+        conn.execute("DELETE FROM collections WHERE id = 1", []).unwrap();
+
+        let remaining: Vec<u32> = conn
+            .prepare("SELECT DISTINCT collection_id FROM cards").unwrap()
+            .query_map([], |row| row.get(0)).unwrap()
+            .collect::<Result<_, _>>().unwrap();
+        assert_eq!(remaining, [2]);
+    }
+
+    #[test]
+    fn deleting_a_section_ungroups_its_cards() {
+        let mut conn = open_v1_fixture();
+        migrate(&mut conn).unwrap();
+        conn.pragma_update(None, "foreign_keys", true).unwrap();
+
+        conn.execute("INSERT INTO sections (collection_id, name) VALUES (1, 'Stadiums')", []).unwrap();
+        conn.execute("UPDATE cards SET section_id = 1 WHERE collection_id = 1", []).unwrap();
+        conn.execute("DELETE FROM sections WHERE id = 1", []).unwrap();
+
+        let grouped: u32 = conn
+            .query_row("SELECT count(*) FROM cards WHERE section_id IS NOT NULL", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(grouped, 0);
+    }
+
+    #[test]
+    fn migrating_twice_is_a_no_op() {
+        let mut conn = open_v1_fixture();
+        migrate(&mut conn).unwrap();
+        migrate(&mut conn).unwrap();
+
+        assert_eq!(schema_version(&conn).unwrap(), LATEST_VERSION);
+    }
+
+    #[test]
+    fn newer_schema_version_is_rejected() {
+        let mut conn = Connection::open_in_memory().unwrap();
+        conn.pragma_update(None, "user_version", LATEST_VERSION + 1).unwrap();
+
+        assert!(matches!(migrate(&mut conn),
+                         Err(StickereError::UnsupportedSchemaVersion(version)) if version == LATEST_VERSION + 1));
+    }
+
+    #[test]
+    fn collection_size_follows_its_cards() {
+        let mut conn = open_v1_fixture();
+        migrate(&mut conn).unwrap();
+        let size = |conn: &Connection| -> u32 {
+            conn.query_row("SELECT size FROM collections WHERE id = 1", [], |row| row.get(0)).unwrap()
+        };
+        assert_eq!(size(&conn), 3);
+
+        conn.execute("INSERT INTO cards (collection_id, card_number, collected) VALUES (1, '4', false)", []).unwrap();
+        assert_eq!(size(&conn), 4);
+        conn.execute("DELETE FROM cards WHERE collection_id = 1 AND card_number IN ('1', '2')", []).unwrap();
+        assert_eq!(size(&conn), 2);
+    }
+
+    #[test]
+    fn cards_sharing_a_label_are_merged() {
+        let mut conn = open_v1_fixture();
+        conn.execute_batch("INSERT INTO cards (collection_id, card_number, collected, duplicates) VALUES (1, 1, 1, 1);
+                            INSERT INTO cards (collection_id, card_number, collected, duplicates) VALUES (1, 2, 0, 0);
+                            INSERT INTO cards (collection_id, card_number, collected, duplicates) VALUES (1, 2, 0, 0);
+                            INSERT INTO cards (collection_id, card_number, collected, duplicates) VALUES (1, 3, 0, 2);").unwrap();
+
+        migrate(&mut conn).unwrap();
+
+        let cards: Vec<(String, bool, u32)> = conn
+            .prepare("SELECT card_number, collected, duplicates FROM cards WHERE collection_id = 1 ORDER BY id").unwrap()
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?))).unwrap()
+            .collect::<Result<_, _>>().unwrap();
+        assert_eq!(cards, [("1".to_string(), true, 4), ("2".to_string(), false, 0), ("3".to_string(), true, 2)]);
+
+        let size: u32 = conn.query_row("SELECT size FROM collections WHERE id = 1", [], |row| row.get(0)).unwrap();
+        assert_eq!(size, 3);
+        assert!(conn.execute("INSERT INTO cards (collection_id, card_number, collected) VALUES (1, '1', false)", [])
+            .is_err());
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::HashSet;
+
+/// The outcome of opening a pack: which of its cards were new and which were duplicates.
+///
+/// # Fields
+/// * `new` - The labels of the cards that were not collected before, in pack order.
+/// * `duplicates` - The labels of the cards that were already collected, in pack order.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct PackSummary {
+    pub new: Vec<String>,
+    pub duplicates: Vec<String>,
+}
+
+/// Represents a pack opened for a collection, as recorded in the pack history.
+///
+/// # Fields
+/// * `id` - A `u32` representing the unique identifier of the pack.
+/// * `opened_at` - A `String` representing the local date and time the pack was opened, as `YYYY-MM-DD HH:MM:SS`.
+/// * `summary` - The `PackSummary` of the stickers found in the pack.
+#[derive(Debug, Clone)]
+pub struct Pack {
+    pub id: u32,
+    pub opened_at: String,
+    pub summary: PackSummary,
+}
+
+/// Statistics over the pack history of a collection.
+///
+/// # Fields
+/// * `packs` - The number of packs opened.
+/// * `new` - The number of stickers that were new when their pack was opened.
+/// * `duplicates` - The number of stickers that were duplicates when their pack was opened.
+#[derive(Debug, Default, PartialEq)]
+pub struct PackStats {
+    pub packs: u32,
+    pub new: u32,
+    pub duplicates: u32,
+}
+
+impl PackStats {
+    /// Sums up the stickers of the given packs.
+    ///
+    /// # Arguments
+    ///
+    /// * `packs` - The packs to sum up.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The statistics of the packs.
+    pub fn from_packs<'a>(packs: impl IntoIterator<Item=&'a Pack>) -> Self {
+        packs.into_iter().fold(Self::default(), |stats, pack| Self {
+            packs: stats.packs + 1,
+            new: stats.new + pack.summary.new.len() as u32,
+            duplicates: stats.duplicates + pack.summary.duplicates.len() as u32,
+        })
+    }
+
+    /// Computes the share of duplicates among all the stickers found in the packs.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<f64>` - The duplicate rate between 0 and 1, or `None` if no sticker was found yet.
+    pub fn duplicate_rate(&self) -> Option<f64> {
+        let stickers = self.new + self.duplicates;
+        (stickers > 0).then(|| self.duplicates as f64 / stickers as f64)
+    }
+
+    /// Computes how much every new sticker cost on average.
+    ///
+    /// # Arguments
+    ///
+    /// * `pack_price` - A `f64` representing the price of a pack.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<f64>` - The cost per new sticker, or `None` if no new sticker was found yet.
+    pub fn cost_per_new(&self, pack_price: f64) -> Option<f64> {
+        (self.new > 0).then(|| self.packs as f64 * pack_price / self.new as f64)
+    }
+}
+
+/// Reads the labels of the cards in a pack, typed separated by spaces or commas, e.g. `3 17, 44 FWC 5`.
+/// Since labels such as `FWC 5` contain a space themselves, a word is joined with the next one
+/// whenever the two together form the label of a card of the collection.
+///
+/// # Arguments
+///
+/// * `text` - A string slice holding the typed labels.
+/// * `labels` - The labels of the cards of the collection.
+///
+/// # Returns
+///
+/// * `Vec<String>` - The labels of the cards in the pack, in the order they were typed.
+pub fn parse_pack(text: &str, labels: &HashSet<&str>) -> Vec<String> {
+    let mut cards = vec![];
+
+    for part in text.split(',') {
+        let mut words = part.split_whitespace().peekable();
+        while let Some(word) = words.next() {
+            let joined = words.peek().map(|next| format!("{word} {next}"));
+            match joined {
+                Some(joined) if labels.contains(joined.as_str()) => {
+                    words.next();
+                    cards.push(joined);
+                }
+                _ => cards.push(word.to_string()),
+            }
+        }
+    }
+
+    cards
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn labels_with_spaces_are_joined() {
+        let labels = HashSet::from(["3", "17", "44", "FWC 5", "FWC", "Logo"]);
+
+        assert_eq!(parse_pack("3 17,44  FWC 5, FWC, Logo 3", &labels),
+                   ["3", "17", "44", "FWC 5", "FWC", "Logo", "3"]);
+    }
+
+    #[test]
+    fn unknown_labels_are_kept_as_typed() {
+        let labels = HashSet::from(["1"]);
+
+        assert_eq!(parse_pack("1 99, BRA 1", &labels), ["1", "99", "BRA", "1"]);
+    }
+
+    fn pack(new: &[&str], duplicates: &[&str]) -> Pack {
+        Pack {
+            id: 0,
+            opened_at: String::new(),
+            summary: PackSummary {
+                new: new.iter().map(|label| label.to_string()).collect(),
+                duplicates: duplicates.iter().map(|label| label.to_string()).collect(),
+            },
+        }
+    }
+
+    #[test]
+    fn stats_sum_up_the_packs() {
+        let packs = [pack(&["1", "2", "3", "4"], &[]), pack(&["5"], &["1", "2", "3"])];
+        let stats = PackStats::from_packs(&packs);
+
+        assert_eq!(stats, PackStats { packs: 2, new: 5, duplicates: 3 });
+        assert_eq!(stats.duplicate_rate(), Some(0.375));
+        assert_eq!(stats.cost_per_new(5.0), Some(2.0));
+    }
+
+    #[test]
+    fn stats_of_no_packs_have_no_rates() {
+        let stats = PackStats::from_packs(&[]);
+
+        assert_eq!(stats.duplicate_rate(), None);
+        assert_eq!(stats.cost_per_new(5.0), None);
+    }
+}
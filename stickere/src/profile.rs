@@ -0,0 +1,302 @@
+use std::env;
+use std::ffi::OsString;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::db::DbHandler;
+use crate::error::StickereError;
+
+/// The environment variable that can hold the path of the database file.
+pub const DB_PATH_VAR: &str = "STICKERE_DB";
+
+/// The profile used when no other is chosen.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// The database file used before profiles existed, relative to the directory of the application
+/// or to the working directory. It is copied to the default profile the first time the default profile is opened.
+const LEGACY_DB_PATH: &str = "db/stick.db";
+
+/// The file of the profiles directory listing the legacy database files already copied to the default profile
+/// or reported as not copied, one path per line.
+const LEGACY_RECORD_FILE: &str = "legacy.txt";
+
+/// The profiles, each with its own database file, kept in a directory.
+///
+/// # Fields
+/// * `dir` - The directory holding a `<profile>.db` file for every profile.
+/// * `legacy_paths` - The places a database file used before profiles existed is looked for, in order.
+#[derive(Debug, Clone)]
+pub struct Profiles {
+    dir: PathBuf,
+    legacy_paths: Vec<PathBuf>,
+}
+
+impl Profiles {
+    /// Creates a new `Profiles` instance for the profiles of a directory.
+    ///
+    /// # Arguments
+    ///
+    /// * `dir` - The directory holding the database files of the profiles.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new instance of `Profiles`.
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir, legacy_paths: vec![] }
+    }
+
+    /// Sets the places a database file used before profiles existed is looked for, in order.
+    ///
+    /// # Arguments
+    ///
+    /// * `legacy_paths` - The paths of the legacy database file to try.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The `Profiles` looking for the legacy database file in these places.
+    pub fn with_legacy_paths(mut self, legacy_paths: Vec<PathBuf>) -> Self {
+        self.legacy_paths = legacy_paths;
+        self
+    }
+
+    /// Creates a new `Profiles` instance for the profiles of the platform data directory,
+    /// e.g. `~/.local/share/stickere` on Linux or `%APPDATA%\stickere` on Windows.
+    /// The working directory is used on platforms without a data directory.
+    /// The legacy database file is looked for next to the application, then in the working directory.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new instance of `Profiles`.
+    pub fn in_data_dir() -> Self {
+        let mut legacy_paths = vec![];
+        if let Some(app_dir) = env::current_exe().ok().as_deref().and_then(Path::parent) {
+            legacy_paths.push(app_dir.join(LEGACY_DB_PATH));
+        }
+        legacy_paths.push(PathBuf::from(LEGACY_DB_PATH));
+
+        Self::new(dirs::data_dir().unwrap_or_default().join("stickere")).with_legacy_paths(legacy_paths)
+    }
+
+    /// Returns the path of the database file of a profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A string slice holding the name of the profile.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<PathBuf, StickereError>` - The path of the database file, or `InvalidProfileName` if the name
+    ///   is empty or has characters other than letters, digits, spaces, `-` and `_`.
+    pub fn path(&self, name: &str) -> Result<PathBuf, StickereError> {
+        let valid = !name.trim().is_empty() && name.chars().all(|c| c.is_alphanumeric() || " -_".contains(c));
+        if !valid {
+            return Err(StickereError::InvalidProfileName(name.to_string()));
+        }
+        Ok(self.dir.join(format!("{name}.db")))
+    }
+
+    /// Lists the profiles, which are the default profile and every profile with a database file, sorted by name.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<String>, StickereError>` - The names of the profiles.
+    pub fn list(&self) -> Result<Vec<String>, StickereError> {
+        let mut names = vec![DEFAULT_PROFILE.to_string()];
+
+        if self.dir.exists() {
+            for entry in fs::read_dir(&self.dir)? {
+                let path = entry?.path();
+                if path.extension().is_some_and(|extension| extension == "db") {
+                    if let Some(name) = path.file_stem().and_then(|stem| stem.to_str()) {
+                        names.push(name.to_string());
+                    }
+                }
+            }
+        }
+
+        names.sort();
+        names.dedup();
+        Ok(names)
+    }
+
+    /// Opens the database of a profile, creating it if the profile is new.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A string slice holding the name of the profile.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<DbHandler, StickereError>` - The database of the profile.
+    pub fn open(&self, name: &str) -> Result<DbHandler, StickereError> {
+        let path = self.path(name)?;
+
+        if name == DEFAULT_PROFILE && !path.exists() {
+            if let Some(legacy_path) = self.legacy_db() {
+                fs::create_dir_all(&self.dir)?;
+                fs::copy(&legacy_path, &path)?;
+                self.record_legacy_db(&legacy_path)?;
+            }
+        }
+
+        DbHandler::new(path)
+    }
+
+    /// Finds a database file used before profiles existed that was not copied to the default profile,
+    /// because the default profile already had a database, and records it so it is reported only once.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Option<PathBuf>, StickereError>` - The path of the legacy database file, if it was not copied.
+    pub fn take_skipped_legacy_db(&self) -> Result<Option<PathBuf>, StickereError> {
+        if !self.path(DEFAULT_PROFILE)?.exists() {
+            return Ok(None);
+        }
+
+        let legacy_path = self.legacy_db();
+        if let Some(legacy_path) = &legacy_path {
+            self.record_legacy_db(legacy_path)?;
+        }
+        Ok(legacy_path)
+    }
+
+    /// Looks for a legacy database file that was neither copied to the default profile nor reported yet.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<PathBuf>` - The absolute path of the legacy database file, if there is one.
+    fn legacy_db(&self) -> Option<PathBuf> {
+        let recorded = fs::read_to_string(self.dir.join(LEGACY_RECORD_FILE)).unwrap_or_default();
+
+        self.legacy_paths.iter()
+            .filter_map(|path| fs::canonicalize(path).ok())
+            .find(|path| !recorded.lines().any(|line| Path::new(line) == path))
+    }
+
+    /// Records a legacy database file as copied or reported, so it is not copied or reported again.
+    ///
+    /// # Arguments
+    ///
+    /// * `legacy_path` - The absolute path of the legacy database file.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn record_legacy_db(&self, legacy_path: &Path) -> Result<(), StickereError> {
+        fs::create_dir_all(&self.dir)?;
+        let mut record = OpenOptions::new().create(true).append(true).open(self.dir.join(LEGACY_RECORD_FILE))?;
+        writeln!(record, "{}", legacy_path.display())?;
+        Ok(())
+    }
+}
+
+/// Where the sticker database is opened from.
+#[derive(Debug, PartialEq)]
+pub enum DbLocation {
+    /// A database file given by its path.
+    File(PathBuf),
+    /// The database of a profile.
+    Profile(String),
+}
+
+impl DbLocation {
+    /// Picks the database to open: the file given on the command line, then the profile given on the command line,
+    /// then the file named by the `STICKERE_DB` environment variable, and finally the default profile.
+    ///
+    /// # Arguments
+    ///
+    /// * `db` - The path of the database file given on the command line, if any.
+    /// * `profile` - The profile given on the command line, if any.
+    /// * `env_db` - The value of the `STICKERE_DB` environment variable, if it is set.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The location of the database.
+    pub fn resolve(db: Option<PathBuf>, profile: Option<String>, env_db: Option<OsString>) -> Self {
+        match (db, profile, env_db) {
+            (Some(path), _, _) => DbLocation::File(path),
+            (None, Some(name), _) => DbLocation::Profile(name),
+            (None, None, Some(path)) if !path.is_empty() => DbLocation::File(PathBuf::from(path)),
+            _ => DbLocation::Profile(DEFAULT_PROFILE.to_string()),
+        }
+    }
+
+    /// Opens the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `profiles` - A reference to the `Profiles` the profile is looked up in.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<DbHandler, StickereError>` - The opened database.
+    pub fn open(&self, profiles: &Profiles) -> Result<DbHandler, StickereError> {
+        match self {
+            DbLocation::File(path) => DbHandler::new(path),
+            DbLocation::Profile(name) => profiles.open(name),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::Storage;
+
+    #[test]
+    fn command_line_comes_before_environment_and_default_profile() {
+        let env_db = Some(OsString::from("/tmp/env.db"));
+
+        assert_eq!(DbLocation::resolve(Some(PathBuf::from("flag.db")), Some("ana".to_string()), env_db.clone()),
+                   DbLocation::File(PathBuf::from("flag.db")));
+        assert_eq!(DbLocation::resolve(None, Some("ana".to_string()), env_db.clone()),
+                   DbLocation::Profile("ana".to_string()));
+        assert_eq!(DbLocation::resolve(None, None, env_db), DbLocation::File(PathBuf::from("/tmp/env.db")));
+        assert_eq!(DbLocation::resolve(None, None, Some(OsString::new())),
+                   DbLocation::Profile(DEFAULT_PROFILE.to_string()));
+        assert_eq!(DbLocation::resolve(None, None, None), DbLocation::Profile(DEFAULT_PROFILE.to_string()));
+    }
+
+    #[test]
+    fn profiles_are_database_files_of_their_directory() {
+        let dir = std::env::temp_dir().join(format!("stickere-profiles-{}", std::process::id()));
+        let profiles = Profiles::new(dir.clone());
+        assert_eq!(profiles.list().unwrap(), [DEFAULT_PROFILE]);
+
+        profiles.open("Maria").unwrap();
+        profiles.open("Andrei").unwrap();
+        assert_eq!(profiles.list().unwrap(), ["Andrei", "Maria", DEFAULT_PROFILE]);
+        assert_eq!(profiles.path("Maria").unwrap(), dir.join("Maria.db"));
+
+        for name in ["", " ", "../tata", "a/b", "c:"] {
+            assert!(matches!(profiles.path(name), Err(StickereError::InvalidProfileName(_))), "{name:?}");
+        }
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn legacy_database_is_copied_to_a_new_default_profile_or_reported_once() {
+        let dir = std::env::temp_dir().join(format!("stickere-legacy-{}", std::process::id()));
+        let legacy_path = dir.join("old").join(LEGACY_DB_PATH);
+        fs::create_dir_all(legacy_path.parent().unwrap()).unwrap();
+        let mut legacy_db = DbHandler::new(&legacy_path).unwrap();
+        legacy_db.add_collection("Euro 2024", 3).unwrap();
+        drop(legacy_db);
+
+        let profiles = Profiles::new(dir.join("copied"))
+            .with_legacy_paths(vec![dir.join("missing.db"), legacy_path.clone()]);
+        assert_eq!(profiles.open(DEFAULT_PROFILE).unwrap().get_collections().unwrap().len(), 1);
+        assert_eq!(profiles.take_skipped_legacy_db().unwrap(), None);
+
+        let profiles = Profiles::new(dir.join("skipped"));
+        profiles.open(DEFAULT_PROFILE).unwrap();
+        let profiles = profiles.with_legacy_paths(vec![legacy_path.clone()]);
+        assert!(profiles.open(DEFAULT_PROFILE).unwrap().get_collections().unwrap().is_empty());
+        assert_eq!(profiles.take_skipped_legacy_db().unwrap(), Some(fs::canonicalize(&legacy_path).unwrap()));
+        assert_eq!(profiles.take_skipped_legacy_db().unwrap(), None);
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
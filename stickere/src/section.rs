@@ -0,0 +1,46 @@
+use crate::card::Card;
+
+/// Represents a section of an album, such as a team or a page, grouping cards of a collection.
+///
+/// # Fields
+/// * `id` - A `u32` representing the unique identifier of the section.
+/// * `name` - A `String` representing the name of the section.
+#[derive(Debug, Clone)]
+pub struct Section {
+    pub id: u32,
+    pub name: String,
+}
+
+impl Section {
+    /// Creates a new `Section` instance.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - A `u32` representing the unique identifier of the section.
+    /// * `name` - A `String` representing the name of the section.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - A new instance of `Section`.
+    pub fn new(id: u32, name: String) -> Self {
+        Self { id, name }
+    }
+
+    /// Counts how many cards of a section are collected.
+    ///
+    /// # Arguments
+    ///
+    /// * `section_id` - The identifier of the section, or `None` for the cards without a section.
+    /// * `cards` - A slice of the `Card` objects of the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `(usize, usize)` - The number of collected cards and the total number of cards in the section.
+    pub fn completion(section_id: Option<u32>, cards: &[Card]) -> (usize, usize) {
+        let section_cards = cards.iter().filter(|card| card.section_id == section_id);
+        let (collected, total) = section_cards.fold((0, 0), |(collected, total), card| {
+            (collected + card.checked as usize, total + 1)
+        });
+        (collected, total)
+    }
+}
@@ -0,0 +1,443 @@
+use crate::backup::Backup;
+use crate::card::Card;
+use crate::checklist::{self, ChecklistCard, ExportFormat};
+use crate::collection::{Collection, DeletedCollection};
+use crate::error::StickereError;
+use crate::pack::{Pack, PackSummary};
+use crate::section::Section;
+use crate::trade::Trade;
+
+/// A copy of a card, taken before it is deleted so it can be added back with its original identifier.
+///
+/// # Fields
+/// * `collection_id` - A `u32` representing the identifier of the collection of the card.
+/// * `card` - The deleted `Card`, with its section and details.
+#[derive(Debug, Clone)]
+pub struct Snapshot {
+    pub collection_id: u32,
+    pub card: Card,
+}
+
+/// The operations on the stored collections and their cards, sections, trades and packs.
+/// It is implemented by the SQLite database, `DbHandler`, and by `MemoryStorage`, which keeps everything in memory.
+pub trait Storage {
+    /// Retrieves all collections that are not in the trash, together with how many of their cards are collected.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Collection>, StickereError>` - A vector of `Collection` objects.
+    fn get_collections(&self) -> Result<Vec<Collection>, StickereError>;
+
+    /// Retrieves all cards from a specific collection, sorted naturally by label.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - A `u32` representing the collection identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Card>, StickereError>` - A vector of `Card` objects.
+    fn get_cards_from_collection(&self, id: u32) -> Result<Vec<Card>, StickereError>;
+
+    /// Updates the collected status of a card.
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - A `u32` representing the card identifier.
+    /// * `status` - A `bool` representing the collected status.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn update_card(&mut self, id: u32, status: bool) -> Result<(), StickereError>;
+
+    /// Adds a new collection to the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A string slice representing the collection name.
+    /// * `size` - A `u32` representing the number of cards in the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u32, StickereError>` - The identifier of the newly added collection.
+    fn add_collection(&mut self, name: &str, size: u32) -> Result<u32, StickereError>;
+
+    /// Renames a collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    /// * `name` - A string slice representing the new name of the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn rename_collection(&mut self, collection_id: u32, name: &str) -> Result<(), StickereError>;
+
+    /// Changes the number of cards of a collection, in a single transaction.
+    /// Growing the collection adds cards labelled after the last added card, shrinking it removes
+    /// the last cards in label order, as long as none of them is collected or has duplicates or details.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    /// * `size` - A `u32` representing the new number of cards.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn resize_collection(&mut self, collection_id: u32, size: u32) -> Result<(), StickereError>;
+
+    /// Moves a collection to the trash. The collection keeps all its data and can be restored until it is purged.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn delete_collection(&mut self, collection_id: u32) -> Result<(), StickereError>;
+
+    /// Restores a collection from the trash.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn restore_collection(&mut self, collection_id: u32) -> Result<(), StickereError>;
+
+    /// Permanently deletes a collection from the trash.
+    /// Its associated cards, sections, trades and packs are removed with it.
+    /// Collections not in the trash are left untouched.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn purge_collection(&mut self, collection_id: u32) -> Result<(), StickereError>;
+
+    /// Retrieves the collections in the trash, most recently deleted first.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<DeletedCollection>, StickereError>` - A vector of `DeletedCollection` objects.
+    fn get_deleted_collections(&self) -> Result<Vec<DeletedCollection>, StickereError>;
+
+    /// Retrieves the name of a specific collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, StickereError>` - The name of the collection.
+    fn get_collection_name(&self, collection_id: u32) -> Result<String, StickereError>;
+
+    /// Infers the label of the next card of a specific collection,
+    /// by incrementing the number of the most recently added label while keeping its prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, StickereError>` - The label of the next card in the collection.
+    fn get_next_label_for_collection(&self, collection_id: u32) -> Result<String, StickereError>;
+
+    /// Adds a new card to a specific collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `card_number` - A string slice representing the label of the card.
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u32, StickereError>` - The identifier of the newly added card.
+    fn add_card(&mut self, card_number: &str, collection_id: u32) -> Result<u32, StickereError>;
+
+    /// Updates the number of duplicates for a specific card.
+    ///
+    /// # Arguments
+    ///
+    /// * `card` - A mutable reference to the `Card` object that needs updated.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn update_card_duplicates(&mut self, card: &mut Card) -> Result<(), StickereError>;
+
+    /// Sets the number of duplicates of a specific card.
+    ///
+    /// # Arguments
+    ///
+    /// * `card_id` - A `u32` representing the card identifier.
+    /// * `duplicates` - A `u32` representing the new number of duplicates.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn set_card_duplicates(&mut self, card_id: u32, duplicates: u32) -> Result<(), StickereError>;
+
+    /// Changes the label of a specific card.
+    ///
+    /// # Arguments
+    ///
+    /// * `card_id` - A `u32` representing the card identifier.
+    /// * `label` - A string slice representing the new label of the card.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn update_card_label(&mut self, card_id: u32, label: &str) -> Result<(), StickereError>;
+
+    /// Deletes a card from the database.
+    ///
+    /// # Arguments
+    ///
+    /// * `card_id` - A `u32` representing the card identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn delete_card(&mut self, card_id: u32) -> Result<(), StickereError>;
+
+    /// Copies a card, so the card can be restored after being deleted.
+    ///
+    /// # Arguments
+    ///
+    /// * `card_id` - A `u32` representing the card identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Snapshot, StickereError>` - The copy of the card, or `CardNotFound` if there is none.
+    fn snapshot_card(&self, card_id: u32) -> Result<Snapshot, StickereError>;
+
+    /// Adds a card copied by `snapshot_card` back, with its original identifier.
+    ///
+    /// # Arguments
+    ///
+    /// * `snapshot` - A reference to the `Snapshot` to restore.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn restore(&mut self, snapshot: &Snapshot) -> Result<(), StickereError>;
+
+    /// Updates the descriptive details of a specific card.
+    ///
+    /// # Arguments
+    ///
+    /// * `card` - A reference to the `Card` object whose details need updated.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn update_card_details(&mut self, card: &Card) -> Result<(), StickereError>;
+
+    /// Retrieves all sections of a specific collection, in the order they were added.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Section>, StickereError>` - A vector of `Section` objects.
+    fn get_sections_from_collection(&self, collection_id: u32) -> Result<Vec<Section>, StickereError>;
+
+    /// Adds a new, empty section to a specific collection.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    /// * `name` - A string slice representing the name of the section.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u32, StickereError>` - The identifier of the newly added section.
+    fn add_section(&mut self, collection_id: u32, name: &str) -> Result<u32, StickereError>;
+
+    /// Moves the cards of a collection whose labels fall between two labels, inclusive,
+    /// in natural order, into a section.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    /// * `section_id` - A `u32` representing the section identifier.
+    /// * `first_label` - A string slice representing the label of the first card of the section.
+    /// * `last_label` - A string slice representing the label of the last card of the section.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<usize, StickereError>` - The number of cards moved into the section.
+    fn assign_cards_to_section(&mut self, collection_id: u32, section_id: u32,
+                               first_label: &str, last_label: &str) -> Result<usize, StickereError>;
+
+    /// Adds a new collection filled with the cards of a checklist, in a single transaction.
+    /// The declared sections are created first, in order, followed by the sections only named
+    /// by cards, in the order they first appear.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - A string slice representing the collection name.
+    /// * `sections` - A slice of the section names declared by the checklist.
+    /// * `cards` - A slice of the validated `ChecklistCard` entries of the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u32, StickereError>` - The identifier of the newly added collection.
+    fn import_collection(&mut self, name: &str, sections: &[String],
+                         cards: &[ChecklistCard]) -> Result<u32, StickereError>;
+
+    /// Exports the full state of a collection, so it can be saved to a file and imported back.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    /// * `format` - The `ExportFormat` to write the collection in.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<String, StickereError>` - The contents of the exported file.
+    fn export_collection(&self, collection_id: u32, format: ExportFormat) -> Result<String, StickereError> {
+        let sections = self.get_sections_from_collection(collection_id)?;
+        let cards: Vec<ChecklistCard> = self.get_cards_from_collection(collection_id)?
+            .into_iter()
+            .map(|card| ChecklistCard {
+                section: sections.iter()
+                    .find(|section| Some(section.id) == card.section_id)
+                    .map(|section| section.name.clone()),
+                label: card.label,
+                details: card.details,
+                collected: card.checked,
+                duplicates: card.duplicates,
+            })
+            .collect();
+
+        match format {
+            ExportFormat::Csv => checklist::to_csv(&cards),
+            ExportFormat::Json => {
+                let name = self.get_collection_name(collection_id)?;
+                let section_names: Vec<String> = sections.into_iter().map(|section| section.name).collect();
+                checklist::to_json(&name, &section_names, &cards)
+            }
+        }
+    }
+
+    /// Marks several cards of a collection as collected or not collected, in a single transaction.
+    /// Nothing is changed if any of the cards does not exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    /// * `labels` - A slice of the labels of the cards.
+    /// * `collected` - A `bool` representing the new collected status.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn set_collected(&mut self, collection_id: u32, labels: &[String], collected: bool) -> Result<(), StickereError>;
+
+    /// Adds or removes duplicates of several cards of a collection, in a single transaction.
+    /// Nothing is changed if any of the cards does not exist or would end up with fewer than no duplicates.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    /// * `changes` - A slice of the labels of the cards, each with the number of duplicates to add, or to remove if negative.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure.
+    fn change_duplicates(&mut self, collection_id: u32, changes: &[(String, i64)]) -> Result<(), StickereError>;
+
+    /// Records a trade made with another collector and applies it to the collection, in a single transaction.
+    /// Every given card loses one duplicate, while every received card is marked as collected,
+    /// or gains a duplicate if it was already collected.
+    /// Nothing is changed if any of the cards does not exist or a given card has no duplicate.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    /// * `partner` - A string slice representing the name of the other collector.
+    /// * `given` - A slice of the labels of the cards given away.
+    /// * `received` - A slice of the labels of the cards received.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<u32, StickereError>` - The identifier of the newly recorded trade.
+    fn record_trade(&mut self, collection_id: u32, partner: &str, given: &[String],
+                    received: &[String]) -> Result<u32, StickereError>;
+
+    /// Marks the cards of an opened pack as received and adds the pack to the pack history, in a single transaction.
+    /// Every card that was not collected yet becomes collected, every other card gains a duplicate.
+    /// Nothing is changed if any of the cards does not exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    /// * `labels` - A slice of the labels of the cards in the pack.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<PackSummary, StickereError>` - Which cards of the pack were new and which were duplicates.
+    fn open_pack(&mut self, collection_id: u32, labels: &[String]) -> Result<PackSummary, StickereError>;
+
+    /// Retrieves the trade history of a specific collection, most recent trade first.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Trade>, StickereError>` - A vector of `Trade` objects.
+    fn get_trades_from_collection(&self, collection_id: u32) -> Result<Vec<Trade>, StickereError>;
+
+    /// Retrieves the packs opened for a specific collection, in the order they were opened.
+    ///
+    /// # Arguments
+    ///
+    /// * `collection_id` - A `u32` representing the collection identifier.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Pack>, StickereError>` - A result containing a vector of `Pack` objects or an error.
+    fn get_packs_from_collection(&self, collection_id: u32) -> Result<Vec<Pack>, StickereError>;
+
+    /// Retrieves the backups the storage can be restored from, most recent first.
+    /// Storage that is not saved to a file has no backups.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<Vec<Backup>, StickereError>` - A vector of `Backup` objects.
+    fn get_backups(&self) -> Result<Vec<Backup>, StickereError> {
+        Ok(vec![])
+    }
+
+    /// Replaces all the stored data with the data of a backup.
+    /// The current data is backed up first, so the restore can itself be undone from the backups.
+    ///
+    /// # Arguments
+    ///
+    /// * `backup` - A reference to the `Backup` to restore.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<(), StickereError>` - A result indicating success or failure, `BackupNotFound` if the backup
+    ///   does not exist.
+    fn restore_backup(&mut self, backup: &Backup) -> Result<(), StickereError> {
+        Err(StickereError::BackupNotFound(backup.path.display().to_string()))
+    }
+}
@@ -0,0 +1,16 @@
+/// Represents a trade made with another collector, as recorded in the trade history of a collection.
+///
+/// # Fields
+/// * `id` - A `u32` representing the unique identifier of the trade.
+/// * `partner` - A `String` representing the name of the other collector.
+/// * `traded_at` - A `String` representing the local date and time of the trade, as `YYYY-MM-DD HH:MM:SS`.
+/// * `given` - The labels of the cards given away.
+/// * `received` - The labels of the cards received.
+#[derive(Debug, Clone)]
+pub struct Trade {
+    pub id: u32,
+    pub partner: String,
+    pub traded_at: String,
+    pub given: Vec<String>,
+    pub received: Vec<String>,
+}
@@ -0,0 +1,442 @@
+use std::fmt;
+
+use crate::card::Card;
+use crate::checklist::ChecklistCard;
+use crate::label;
+
+/// The largest range accepted by the parser, to guard against typos such as `1-10000`.
+const MAX_RANGE_LENGTH: u64 = 1000;
+
+/// A problem found while parsing a trade list or a list of labels.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TradeListError {
+    /// A part of the list starts with a keyword other than `Need:` or `Got:`.
+    UnknownKeyword(String),
+    /// The number of duplicates of an entry, e.g. the `x2` of `5x2`, is not a number.
+    InvalidDuplicateCount(String),
+    /// An entry comes before any `Need:` or `Got:` keyword.
+    MissingKeyword(String),
+    /// A range is reversed, e.g. `19-12`, or longer than the parser accepts.
+    InvalidRange(String),
+}
+
+impl fmt::Display for TradeListError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TradeListError::UnknownKeyword(keyword) => write!(f, "unknown keyword \"{keyword}\""),
+            TradeListError::InvalidDuplicateCount(entry) => write!(f, "invalid number of duplicates in \"{entry}\""),
+            TradeListError::MissingKeyword(entry) => write!(f, "missing \"Need:\" or \"Got:\" before \"{entry}\""),
+            TradeListError::InvalidRange(entry) => write!(f, "invalid range \"{entry}\""),
+        }
+    }
+}
+
+/// A collector's trade list: the cards they still need and the duplicates they can give away.
+/// It is written in the compact format collectors post to each other, e.g.
+/// `Need: 3, 7, 12-19, 44 / Got: 5x2, 18`, where ranges cover consecutive labels
+/// and `x2` gives the number of duplicates of a card.
+///
+/// # Fields
+/// * `needs` - The labels of the cards that are not collected yet, sorted naturally.
+/// * `doubles` - The labels of the cards with duplicates and the number of duplicates, sorted naturally.
+#[derive(Debug, Default, PartialEq)]
+pub struct TradeList {
+    pub needs: Vec<String>,
+    pub doubles: Vec<(String, u32)>,
+}
+
+impl TradeList {
+    /// Builds the trade list of a collection from its cards.
+    ///
+    /// # Arguments
+    ///
+    /// * `cards` - A slice of the `Card` objects of the collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The trade list of the collection.
+    pub fn from_cards(cards: &[Card]) -> Self {
+        let mut needs: Vec<String> = cards.iter()
+            .filter(|card| !card.checked)
+            .map(|card| card.label.clone())
+            .collect();
+        let mut doubles: Vec<(String, u32)> = cards.iter()
+            .filter(|card| card.duplicates > 0)
+            .map(|card| (card.label.clone(), card.duplicates))
+            .collect();
+
+        needs.sort_by(|a, b| label::natural_cmp(a, b));
+        doubles.sort_by(|(a, _), (b, _)| label::natural_cmp(a, b));
+        Self { needs, doubles }
+    }
+
+    /// Builds the trade list of a collection exported by another collector.
+    ///
+    /// # Arguments
+    ///
+    /// * `cards` - A slice of the `ChecklistCard` entries of the exported collection.
+    ///
+    /// # Returns
+    ///
+    /// * `Self` - The trade list of the exported collection.
+    pub fn from_checklist(cards: &[ChecklistCard]) -> Self {
+        let mut needs: Vec<String> = cards.iter()
+            .filter(|card| !card.collected)
+            .map(|card| card.label.clone())
+            .collect();
+        let mut doubles: Vec<(String, u32)> = cards.iter()
+            .filter(|card| card.duplicates > 0)
+            .map(|card| (card.label.clone(), card.duplicates))
+            .collect();
+
+        needs.sort_by(|a, b| label::natural_cmp(a, b));
+        doubles.sort_by(|(a, _), (b, _)| label::natural_cmp(a, b));
+        Self { needs, doubles }
+    }
+
+    /// Finds the best swap between this trade list, ours, and the trade list of another collector.
+    /// Labels are matched ignoring case and whitespace, so `BRA 5` matches `bra5`,
+    /// and the result always uses our own labels.
+    ///
+    /// # Arguments
+    ///
+    /// * `theirs` - A reference to the `TradeList` of the other collector.
+    ///
+    /// # Returns
+    ///
+    /// * `TradeMatch` - The cards each side can give the other and the suggested balanced trade.
+    pub fn match_with(&self, theirs: &TradeList) -> TradeMatch {
+        let their_needs: Vec<String> = theirs.needs.iter().map(|label| normalize(label)).collect();
+        let their_doubles: Vec<String> = theirs.doubles.iter().map(|(label, _)| normalize(label)).collect();
+
+        let give: Vec<String> = self.doubles.iter()
+            .filter(|(label, _)| their_needs.contains(&normalize(label)))
+            .map(|(label, _)| label.clone())
+            .collect();
+        let get: Vec<String> = self.needs.iter()
+            .filter(|label| their_doubles.contains(&normalize(label)))
+            .cloned()
+            .collect();
+
+        let balanced = give.len().min(get.len());
+        TradeMatch {
+            balanced_give: give[..balanced].to_vec(),
+            balanced_get: get[..balanced].to_vec(),
+            give,
+            get,
+        }
+    }
+
+    /// Formats the needed cards, compressing consecutive labels into ranges.
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The needed cards, e.g. `3, 7, 12-19, 44`.
+    pub fn needs_text(&self) -> String {
+        format_labels(&self.needs)
+    }
+
+    /// Formats the duplicates, compressing consecutive labels with a single duplicate into ranges.
+    ///
+    /// # Returns
+    ///
+    /// * `String` - The duplicates, e.g. `5x2, 18, 20-23`.
+    pub fn doubles_text(&self) -> String {
+        let mut parts = vec![];
+        let mut singles: Vec<&str> = vec![];
+
+        for (label, count) in &self.doubles {
+            if *count == 1 {
+                singles.push(label);
+                continue;
+            }
+            parts.extend(compress(singles.drain(..)));
+            if label.ends_with(|c: char| c.is_ascii_digit()) {
+                parts.push(format!("{label}x{count}"));
+            } else {
+                parts.push(format!("{label} x{count}"));
+            }
+        }
+        parts.extend(compress(singles.drain(..)));
+
+        parts.join(", ")
+    }
+
+    /// Parses a trade list written in the format produced by `Display`.
+    /// Either part may be left out, the keywords are case-insensitive, the parts may also be
+    /// on separate lines and commas, semicolons or new lines separate the entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `text` - A string slice holding the trade list.
+    ///
+    /// # Returns
+    ///
+    /// * `Result<TradeList, TradeListError>` - The parsed trade list, or the problem with the first invalid entry.
+    pub fn parse(text: &str) -> Result<TradeList, TradeListError> {
+        let mut trade_list = TradeList::default();
+        let mut is_need = None;
+
+        for part in text.split(['/', '\n']) {
+            let entries = match part.split_once(':') {
+                Some((keyword, entries)) => {
+                    is_need = match keyword.trim().to_lowercase().as_str() {
+                        "need" | "needs" => Some(true),
+                        "got" | "doubles" => Some(false),
+                        other => return Err(TradeListError::UnknownKeyword(other.to_string())),
+                    };
+                    entries
+                }
+                None => part,
+            };
+
+            for entry in entries_of(entries) {
+                match is_need {
+                    Some(true) => trade_list.needs.extend(expand(entry)?),
+                    Some(false) => {
+                        let (labels, count) = match entry.rsplit_once(['x', 'X']) {
+                            Some((label, count)) if is_count_suffix(label, count) => {
+                                let count = count.trim().parse::<u32>()
+                                    .map_err(|_| TradeListError::InvalidDuplicateCount(entry.to_string()))?;
+                                (expand(label.trim())?, count)
+                            }
+                            _ => (expand(entry)?, 1),
+                        };
+                        trade_list.doubles.extend(labels.into_iter().map(|label| (label, count)));
+                    }
+                    None => return Err(TradeListError::MissingKeyword(entry.to_string())),
+                }
+            }
+        }
+
+        Ok(trade_list)
+    }
+}
+
+impl fmt::Display for TradeList {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Need: {} / Got: {}", self.needs_text(), self.doubles_text())
+    }
+}
+
+/// The result of matching our trade list against the trade list of another collector.
+///
+/// # Fields
+/// * `give` - Our duplicates that the other collector needs.
+/// * `get` - The other collector's duplicates that we need.
+/// * `balanced_give` - Our side of the suggested 1:1 trade.
+/// * `balanced_get` - Their side of the suggested 1:1 trade.
+#[derive(Debug, PartialEq)]
+pub struct TradeMatch {
+    pub give: Vec<String>,
+    pub get: Vec<String>,
+    pub balanced_give: Vec<String>,
+    pub balanced_get: Vec<String>,
+}
+
+/// Formats a list of labels, compressing consecutive labels into ranges.
+///
+/// # Arguments
+///
+/// * `labels` - A slice of labels, sorted naturally.
+///
+/// # Returns
+///
+/// * `String` - The labels, e.g. `3, 7, 12-19, 44`.
+pub fn format_labels(labels: &[String]) -> String {
+    compress(labels.iter().map(String::as_str)).join(", ")
+}
+
+/// Parses a list of labels separated by commas or semicolons, expanding ranges such as `12-19`.
+///
+/// # Arguments
+///
+/// * `text` - A string slice holding the list of labels.
+///
+/// # Returns
+///
+/// * `Result<Vec<String>, TradeListError>` - The labels, or the problem with the first invalid entry.
+pub fn parse_labels(text: &str) -> Result<Vec<String>, TradeListError> {
+    let mut labels = vec![];
+    for entry in entries_of(text) {
+        labels.extend(expand(entry)?);
+    }
+    Ok(labels)
+}
+
+/// Normalizes a label for matching against the labels of another collector.
+fn normalize(label: &str) -> String {
+    label.chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// Splits the entries of one part of a trade list.
+fn entries_of(text: &str) -> impl Iterator<Item=&str> {
+    text.split([',', ';'])
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+}
+
+/// Checks whether an entry split at its last `x` is a label followed by a number of duplicates,
+/// as in `5x2` or `Logo x2`, rather than a label containing an `x`, as in `MEX 5`.
+fn is_count_suffix(label: &str, count: &str) -> bool {
+    let ends_label = label.ends_with(|c: char| c.is_ascii_digit() || c.is_whitespace());
+    ends_label && !label.trim().is_empty() && is_number(count.trim())
+}
+
+/// Checks whether a string is a non-empty run of ASCII digits.
+fn is_number(text: &str) -> bool {
+    !text.is_empty() && text.chars().all(|c| c.is_ascii_digit())
+}
+
+/// Splits a label into its prefix and the number at its end.
+///
+/// # Returns
+///
+/// * `Option<(&str, u64, usize)>` - The prefix, the number and the width of the number,
+///   or `None` if the label does not end in a number.
+fn split_label(label: &str) -> Option<(&str, u64, usize)> {
+    let prefix = label.trim_end_matches(|c: char| c.is_ascii_digit());
+    let digits = &label[prefix.len()..];
+    digits.parse().ok().map(|number| (prefix, number, digits.len()))
+}
+
+/// Compresses runs of three or more consecutive labels, sharing a prefix and padding, into ranges
+/// such as `12-19` or `BRA 3-7`. The labels must already be sorted naturally.
+fn compress<'a>(labels: impl Iterator<Item=&'a str>) -> Vec<String> {
+    let mut parts = vec![];
+    let mut run: Vec<&str> = vec![];
+
+    let flush = |run: &mut Vec<&str>, parts: &mut Vec<String>| {
+        if run.len() >= 3 {
+            let last = run[run.len() - 1];
+            let (prefix, _, _) = split_label(last).expect("runs only hold numbered labels");
+            parts.push(format!("{}-{}", run[0], &last[prefix.len()..]));
+        } else {
+            parts.extend(run.iter().map(|label| label.to_string()));
+        }
+        run.clear();
+    };
+
+    for label in labels {
+        let continues_run = match (run.last().and_then(|last| split_label(last)), split_label(label)) {
+            (Some((last_prefix, last, last_width)), Some((prefix, number, width))) => {
+                prefix == last_prefix && number == last + 1 && (width == last_width || !label[prefix.len()..].starts_with('0'))
+            }
+            _ => false,
+        };
+
+        if !continues_run {
+            flush(&mut run, &mut parts);
+        }
+        if split_label(label).is_some() {
+            run.push(label);
+        } else {
+            parts.push(label.to_string());
+        }
+    }
+    flush(&mut run, &mut parts);
+
+    parts
+}
+
+/// Expands a single entry of a trade list, which is either a label or a range such as `12-19` or `BRA 3-7`.
+fn expand(entry: &str) -> Result<Vec<String>, TradeListError> {
+    let Some((start, end)) = entry.rsplit_once('-').filter(|(start, end)| {
+        is_number(end.trim()) && start.trim_end().ends_with(|c: char| c.is_ascii_digit())
+    }) else {
+        return Ok(vec![entry.to_string()]);
+    };
+
+    let start = start.trim_end();
+    let (prefix, first, width) = split_label(start).ok_or_else(|| TradeListError::InvalidRange(entry.to_string()))?;
+    let last: u64 = end.trim().parse().map_err(|_| TradeListError::InvalidRange(entry.to_string()))?;
+    if last < first || last - first >= MAX_RANGE_LENGTH {
+        return Err(TradeListError::InvalidRange(entry.to_string()));
+    }
+
+    Ok((first..=last).map(|number| format!("{prefix}{number:0width$}")).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::card_details::CardDetails;
+
+    fn card(label: &str, checked: bool, duplicates: u32) -> Card {
+        Card::new(label.to_string(), 0, checked, duplicates, None, CardDetails::default())
+    }
+
+    fn labels(labels: &[&str]) -> Vec<String> {
+        labels.iter().map(|label| label.to_string()).collect()
+    }
+
+    #[test]
+    fn lists_are_range_compressed() {
+        let mut cards: Vec<Card> = (1..=44).map(|number| {
+            let needed = [3, 7, 44].contains(&number) || (12..=19).contains(&number);
+            let duplicates = match number {
+                5 => 2,
+                18 | 20..=23 => 1,
+                _ => 0,
+            };
+            card(&number.to_string(), !needed, duplicates)
+        }).collect();
+        cards.reverse();
+
+        let trade_list = TradeList::from_cards(&cards);
+        assert_eq!(trade_list.to_string(), "Need: 3, 7, 12-19, 44 / Got: 5x2, 18, 20-23");
+    }
+
+    #[test]
+    fn prefixed_labels_keep_their_prefix_in_ranges() {
+        let trade_list = TradeList {
+            needs: labels(&["00", "01", "02", "BRA 3", "BRA 4", "BRA 5", "FWC 1", "Logo"]),
+            doubles: vec![],
+        };
+        assert_eq!(trade_list.needs_text(), "00-02, BRA 3-5, FWC 1, Logo");
+    }
+
+    #[test]
+    fn parsed_list_matches_the_formatted_one() {
+        let trade_list = TradeList {
+            needs: labels(&["3", "7", "12", "13", "14", "BRA 1", "BRA 2", "BRA 3"]),
+            doubles: vec![("5".to_string(), 2), ("18".to_string(), 1), ("20".to_string(), 1),
+                          ("21".to_string(), 1), ("22".to_string(), 1), ("FWC 9".to_string(), 3)],
+        };
+
+        assert_eq!(TradeList::parse(&trade_list.to_string()).unwrap(), trade_list);
+    }
+
+    #[test]
+    fn matching_finds_a_balanced_trade() {
+        let ours = TradeList {
+            needs: labels(&["3", "12", "BRA 7"]),
+            doubles: vec![("1".to_string(), 2), ("5".to_string(), 1), ("9".to_string(), 1)],
+        };
+        let theirs = TradeList::parse("Need: 1, 5, 9, 40 / Got: bra7x3, 12, 20").unwrap();
+
+        let trade = ours.match_with(&theirs);
+        assert_eq!(trade.give, labels(&["1", "5", "9"]));
+        assert_eq!(trade.get, labels(&["12", "BRA 7"]));
+        assert_eq!(trade.balanced_give, labels(&["1", "5"]));
+        assert_eq!(trade.balanced_get, labels(&["12", "BRA 7"]));
+    }
+
+    #[test]
+    fn parser_accepts_loose_input() {
+        let trade_list = TradeList::parse("need: 1;2\ngot: 10 x 2, MEX 5, Logo x3").unwrap();
+        assert_eq!(trade_list.needs, labels(&["1", "2"]));
+        assert_eq!(trade_list.doubles, [("10".to_string(), 2), ("MEX 5".to_string(), 1), ("Logo".to_string(), 3)]);
+
+        assert_eq!(TradeList::parse("Need: 19-12"), Err(TradeListError::InvalidRange("19-12".to_string())));
+        assert_eq!(TradeList::parse("Need: 99999999999999999999-1"),
+                   Err(TradeListError::InvalidRange("99999999999999999999-1".to_string())));
+        assert_eq!(parse_labels("BRA 99999999999999999999-3"),
+                   Err(TradeListError::InvalidRange("BRA 99999999999999999999-3".to_string())));
+        assert_eq!(TradeList::parse("Want: 1"), Err(TradeListError::UnknownKeyword("want".to_string())));
+        assert_eq!(TradeList::parse("1, 2, 3"), Err(TradeListError::MissingKeyword("1".to_string())));
+    }
+}
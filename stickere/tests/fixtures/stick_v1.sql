@@ -0,0 +1,31 @@
+-- A database as created by Stickere 1.0.0, before schema versioning existed.
+-- It has no `user_version` set, so it is reported as version 0.
+-- Foreign keys are off so the orphaned card at the end can be recreated.
+PRAGMA foreign_keys = OFF;
+
+CREATE TABLE collections (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    name TEXT NOT NULL,
+    size INTEGER
+);
+
+CREATE TABLE cards (
+    id INTEGER PRIMARY KEY AUTOINCREMENT,
+    collection_id INTEGER NOT NULL,
+    card_number INTEGER NOT NULL,
+    collected BOOLEAN NOT NULL,
+    duplicates INTEGER DEFAULT 0,
+    FOREIGN KEY (collection_id) REFERENCES collections(id)
+);
+
+INSERT INTO collections (name, size) VALUES ('Euro 2024', 3);
+INSERT INTO collections (name, size) VALUES ('Champions League', 2);
+
+INSERT INTO cards (collection_id, card_number, collected, duplicates) VALUES (1, 1, 1, 2);
+INSERT INTO cards (collection_id, card_number, collected, duplicates) VALUES (1, 2, 0, 0);
+INSERT INTO cards (collection_id, card_number, collected, duplicates) VALUES (1, 3, 1, 0);
+INSERT INTO cards (collection_id, card_number, collected, duplicates) VALUES (2, 1, 0, 0);
+INSERT INTO cards (collection_id, card_number, collected, duplicates) VALUES (2, 2, 1, 1);
+
+-- Left behind by a collection whose deletion was interrupted after removing the collection row.
+INSERT INTO cards (collection_id, card_number, collected, duplicates) VALUES (3, 1, 1, 4);